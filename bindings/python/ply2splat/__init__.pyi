@@ -15,7 +15,7 @@ Example:
     ...     print(splat.position, splat.color)
 """
 
-from typing import Tuple, List, Iterator
+from typing import Tuple, List, Iterator, Optional, Union
 
 
 class Splat:
@@ -26,6 +26,7 @@ class Splat:
         scale: A tuple (x, y, z) of float values representing the splat scale.
         color: A tuple (R, G, B, A) of integers 0-255 representing the splat color.
         rotation: A tuple (r0, r1, r2, r3) of integers 0-255 representing the encoded quaternion.
+        normal: A tuple (nx, ny, nz), or None when the data was loaded without normals.
     """
     
     @property
@@ -48,6 +49,54 @@ class Splat:
         """The rotation quaternion (r0, r1, r2, r3) encoded as 0-255."""
         ...
 
+    @property
+    def normal(self) -> Optional[Tuple[float, float, float]]:
+        """The normal (nx, ny, nz), or None when the data was loaded without normals."""
+        ...
+
+    def set_position(self, position: Tuple[float, float, float]) -> None:
+        """Move this splat. Splats are copies; store them back with `data[i] = splat`."""
+        ...
+
+    def set_color(self, color: Tuple[int, int, int, int]) -> None:
+        """Recolor this splat. Splats are copies; store them back with `data[i] = splat`."""
+        ...
+
+
+class SplatStats:
+    """Summary statistics of a collection of splats.
+
+    Attributes:
+        count: The number of splats.
+        min: Minimum corner (x, y, z) of the bounding box of the splat centers.
+        max: Maximum corner (x, y, z) of the bounding box.
+        mean_scale: Mean scale (x, y, z).
+        max_scale: Largest scale along any axis.
+        mean_opacity: Mean opacity in [0, 1].
+        opacity_histogram: Splat counts for ten opacity buckets of width 0.1.
+    """
+
+    @property
+    def count(self) -> int: ...
+
+    @property
+    def min(self) -> Tuple[float, float, float]: ...
+
+    @property
+    def max(self) -> Tuple[float, float, float]: ...
+
+    @property
+    def mean_scale(self) -> Tuple[float, float, float]: ...
+
+    @property
+    def max_scale(self) -> float: ...
+
+    @property
+    def mean_opacity(self) -> float: ...
+
+    @property
+    def opacity_histogram(self) -> List[int]: ...
+
 
 class SplatData:
     """A collection of Gaussian Splats loaded from a file.
@@ -71,16 +120,80 @@ class SplatData:
         """Get a splat by index. Supports negative indexing."""
         ...
     
+    def __setitem__(self, index: int, splat: Splat) -> None:
+        """Replace a splat by index. Supports negative indexing."""
+        ...
+
     def __iter__(self) -> Iterator[Splat]:
         """Iterate over all splats."""
         ...
+
+    def set_position(self, index: int, position: Tuple[float, float, float]) -> None:
+        """Move the splat at `index` to `position` (x, y, z).
+
+        Raises:
+            IndexError: If `index` is out of range
+        """
+        ...
+
+    def set_color(self, index: int, color: Tuple[int, int, int, int]) -> None:
+        """Recolor the splat at `index` with `color` (R, G, B, A), values 0-255.
+
+        Raises:
+            IndexError: If `index` is out of range
+        """
+        ...
     
     def to_list(self) -> List[Splat]:
         """Get all splats as a list."""
         ...
     
-    def to_bytes(self) -> bytes:
-        """Get the raw bytes representation of all splats (32 bytes per splat)."""
+    def to_bytes(self, format: str = "splat") -> bytes:
+        """Get the raw bytes representation of all splats.
+
+        Args:
+            format: "splat" for 32-byte records, or "compact16" for 20-byte records with
+                    half-float positions and scales. Readers must use the matching stride.
+
+        Raises:
+            ValueError: If the format is unknown
+        """
+        ...
+
+    def stats(self) -> SplatStats:
+        """Compute bounding box, scale and opacity statistics of the splats."""
+        ...
+
+    def save(self, path: str) -> None:
+        """Save all splats to a SPLAT file.
+
+        Raises:
+            IOError: If the file cannot be written
+        """
+        ...
+
+    @property
+    def normals(self) -> Optional[List[Tuple[float, float, float]]]:
+        """Per-splat normals (nx, ny, nz), or None when loaded without normals."""
+        ...
+
+    def save_normals(self, path: str) -> None:
+        """Save the normals to a sidecar file of little-endian f32 triples in splat order.
+
+        Raises:
+            ValueError: If the data was loaded without normals
+            IOError: If the file cannot be written
+        """
+        ...
+
+    @classmethod
+    def from_bytes(cls, data: bytes, format: str = "splat") -> "SplatData":
+        """Create splat data from raw SPLAT bytes, as returned by `to_bytes` with the same `format`.
+
+        Raises:
+            ValueError: If the format is unknown, or the length of `data` is not a multiple of
+                        the record size (32 bytes, or 20 for "compact16")
+        """
         ...
 
 
@@ -107,6 +220,33 @@ def convert(input_path: str, output_path: str, sort: bool = True) -> int:
     ...
 
 
+def convert_batch(
+    input_paths: List[str], output_dir: str, sort: bool = True
+) -> List[Union[int, IOError]]:
+    """
+    Convert many PLY files into a directory, in parallel and without holding the GIL.
+
+    Each input is written to `<output_dir>/<file stem>.splat`; inputs sharing a file stem
+    fail instead of overwriting each other. The output directory is created if needed. A failing file does
+    not stop the others.
+
+    Args:
+        input_paths: Paths of the input PLY files
+        output_dir: Directory for the output SPLAT files
+        sort: Whether to sort splats by importance (volume * opacity).
+              Defaults to True.
+
+    Returns:
+        One entry per input, in order: the number of splats converted, or an IOError
+        instance (not raised) describing why that file failed
+
+    Example:
+        >>> results = ply2splat.convert_batch(["a.ply", "b.ply"], "out")
+        >>> failed = [r for r in results if isinstance(r, Exception)]
+    """
+    ...
+
+
 def load_and_convert(input_path: str, sort: bool = True) -> Tuple[bytes, int]:
     """
     Load a PLY file and return splat data as bytes.
@@ -134,7 +274,7 @@ def load_and_convert(input_path: str, sort: bool = True) -> Tuple[bytes, int]:
     ...
 
 
-def load_ply_file(input_path: str, sort: bool = True) -> SplatData:
+def load_ply_file(input_path: str, sort: bool = True, normals: bool = False) -> SplatData:
     """
     Load a PLY file and return structured splat data.
 
@@ -145,6 +285,7 @@ def load_ply_file(input_path: str, sort: bool = True) -> SplatData:
         input_path: Path to the input PLY file
         sort: Whether to sort splats by importance (volume * opacity).
               Defaults to True. Sorting improves rendering quality but takes longer.
+        normals: Whether to keep the per-splat normals (nx, ny, nz). Defaults to False.
 
     Returns:
         A SplatData object containing all splats
@@ -184,3 +325,49 @@ def load_splat_file(input_path: str) -> SplatData:
         >>> print(first_splat.position)
     """
     ...
+
+
+def convert_bytes(ply_bytes: bytes, sort: bool = True) -> Tuple[bytes, int]:
+    """
+    Convert in-memory PLY data to the compact SPLAT binary format.
+
+    Args:
+        ply_bytes: Contents of a Gaussian Splatting PLY file
+        sort: Whether to sort splats by importance (volume * opacity).
+              Defaults to True. Sorting improves rendering quality but takes longer.
+
+    Returns:
+        A tuple of (bytes, count) where bytes is the raw SPLAT data (32 bytes per splat)
+        and count is the number of splats
+
+    Raises:
+        ValueError: If the data is not a valid Gaussian Splatting PLY
+
+    Example:
+        >>> data, count = ply2splat.convert_bytes(ply_bytes)
+        >>> print(f"Converted {count} splats, {len(data)} bytes")
+    """
+    ...
+
+
+def load_ply_bytes(ply_bytes: bytes, sort: bool = True, normals: bool = False) -> SplatData:
+    """
+    Load in-memory PLY data and return structured splat data.
+
+    Args:
+        ply_bytes: Contents of a Gaussian Splatting PLY file
+        sort: Whether to sort splats by importance (volume * opacity).
+              Defaults to True. Sorting improves rendering quality but takes longer.
+        normals: Whether to keep the per-splat normals (nx, ny, nz). Defaults to False.
+
+    Returns:
+        A SplatData object containing all splats
+
+    Raises:
+        ValueError: If the data is not a valid Gaussian Splatting PLY
+
+    Example:
+        >>> data = ply2splat.load_ply_bytes(ply_bytes)
+        >>> data.save("scene.splat")
+    """
+    ...
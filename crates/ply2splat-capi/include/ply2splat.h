@@ -0,0 +1,85 @@
+#ifndef PLY2SPLAT_H
+#define PLY2SPLAT_H
+
+/* Generated by cbindgen from crates/ply2splat-capi. Do not edit manually. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * The call succeeded.
+ */
+#define P2S_OK 0
+
+/**
+ * A required pointer argument was null.
+ */
+#define P2S_ERR_NULL_POINTER -1
+
+/**
+ * The input could not be parsed as a Gaussian Splatting PLY.
+ */
+#define P2S_ERR_PARSE -2
+
+/**
+ * An unexpected internal error (panic) occurred.
+ */
+#define P2S_ERR_INTERNAL -3
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Converts PLY data to SPLAT data.
+ *
+ * On success, `*out_ptr` points to `*out_len` bytes of SPLAT data (32 bytes per splat)
+ * which must be released with `p2s_free`. On failure, `*out_ptr` is set to null and
+ * `*out_len` to 0.
+ *
+ * # Arguments
+ * * `ply_ptr` - Pointer to the PLY file contents.
+ * * `ply_len` - Length of the PLY data in bytes.
+ * * `sort` - If true, sorts the splats by importance (volume * opacity).
+ * * `out_ptr` - Receives the pointer to the SPLAT data.
+ * * `out_len` - Receives the length of the SPLAT data in bytes.
+ *
+ * # Returns
+ * `P2S_OK` on success or a negative `P2S_ERR_*` code.
+ *
+ * # Safety
+ * `ply_ptr` must be valid for reads of `ply_len` bytes, and `out_ptr`/`out_len` must be
+ * valid for writes.
+ */
+int32_t p2s_convert(const uint8_t *ply_ptr,
+                    size_t ply_len,
+                    bool sort,
+                    uint8_t **out_ptr,
+                    size_t *out_len);
+
+/**
+ * Releases a buffer returned by `p2s_convert`.
+ *
+ * Passing a null pointer is a no-op.
+ *
+ * # Safety
+ * `ptr` and `len` must be exactly the values returned by a previous successful call,
+ * and the buffer must not have been freed already.
+ */
+void p2s_free(uint8_t *ptr, size_t len);
+
+/**
+ * Returns a static, null-terminated description of an error code.
+ *
+ * The returned string must not be freed.
+ */
+const char *p2s_error_message(int32_t code);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* PLY2SPLAT_H */
@@ -0,0 +1,118 @@
+//! C ABI bindings for ply2splat.
+//!
+//! This crate exposes the core conversion as plain `extern "C"` functions so that any
+//! FFI-capable language (C, C++, C#, ...) can use it without the Node.js or Python layers.
+//! The matching header `include/ply2splat.h` is generated by cbindgen with
+//! `scripts/misc/generate_capi_header.sh`; rerun it after changing the `extern "C"` items.
+//!
+//! ## Error codes
+//!
+//! Every function returning `int32_t` uses `P2S_OK` (0) for success and a negative
+//! `P2S_ERR_*` code on failure. `p2s_error_message` maps a code to a static description.
+//!
+//! ## Ownership
+//!
+//! Buffers returned through `out_ptr`/`out_len` are allocated by Rust and owned by the caller
+//! until they are passed back to `p2s_free` together with the exact length that was returned.
+//! They must not be released with `free()` or any other allocator, and must not be freed twice.
+//! Input buffers are only borrowed for the duration of the call.
+
+use std::ffi::c_char;
+use std::panic::{AssertUnwindSafe, catch_unwind};
+
+/// The call succeeded.
+pub const P2S_OK: i32 = 0;
+/// A required pointer argument was null.
+pub const P2S_ERR_NULL_POINTER: i32 = -1;
+/// The input could not be parsed as a Gaussian Splatting PLY.
+pub const P2S_ERR_PARSE: i32 = -2;
+/// An unexpected internal error (panic) occurred.
+pub const P2S_ERR_INTERNAL: i32 = -3;
+
+/// Converts PLY data to SPLAT data.
+///
+/// On success, `*out_ptr` points to `*out_len` bytes of SPLAT data (32 bytes per splat)
+/// which must be released with `p2s_free`. On failure, `*out_ptr` is set to null and
+/// `*out_len` to 0.
+///
+/// # Arguments
+/// * `ply_ptr` - Pointer to the PLY file contents.
+/// * `ply_len` - Length of the PLY data in bytes.
+/// * `sort` - If true, sorts the splats by importance (volume * opacity).
+/// * `out_ptr` - Receives the pointer to the SPLAT data.
+/// * `out_len` - Receives the length of the SPLAT data in bytes.
+///
+/// # Returns
+/// `P2S_OK` on success or a negative `P2S_ERR_*` code.
+///
+/// # Safety
+/// `ply_ptr` must be valid for reads of `ply_len` bytes, and `out_ptr`/`out_len` must be
+/// valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn p2s_convert(
+    ply_ptr: *const u8,
+    ply_len: usize,
+    sort: bool,
+    out_ptr: *mut *mut u8,
+    out_len: *mut usize,
+) -> i32 {
+    if ply_ptr.is_null() || out_ptr.is_null() || out_len.is_null() {
+        return P2S_ERR_NULL_POINTER;
+    }
+
+    // SAFETY: checked for null above; validity is guaranteed by the caller.
+    unsafe {
+        *out_ptr = std::ptr::null_mut();
+        *out_len = 0;
+    }
+
+    let ply_data = unsafe { std::slice::from_raw_parts(ply_ptr, ply_len) };
+    let result = catch_unwind(AssertUnwindSafe(|| ply2splat::convert(ply_data, sort)));
+
+    match result {
+        Ok(Ok((bytes, _count))) => {
+            let bytes = bytes.into_boxed_slice();
+            let len = bytes.len();
+            unsafe {
+                *out_ptr = Box::into_raw(bytes) as *mut u8;
+                *out_len = len;
+            }
+            P2S_OK
+        }
+        Ok(Err(_)) => P2S_ERR_PARSE,
+        Err(_) => P2S_ERR_INTERNAL,
+    }
+}
+
+/// Releases a buffer returned by `p2s_convert`.
+///
+/// Passing a null pointer is a no-op.
+///
+/// # Safety
+/// `ptr` and `len` must be exactly the values returned by a previous successful call,
+/// and the buffer must not have been freed already.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn p2s_free(ptr: *mut u8, len: usize) {
+    if ptr.is_null() {
+        return;
+    }
+    // SAFETY: the pointer/length pair originates from `Box<[u8]>::into_raw` in `p2s_convert`.
+    unsafe {
+        drop(Box::from_raw(std::ptr::slice_from_raw_parts_mut(ptr, len)));
+    }
+}
+
+/// Returns a static, null-terminated description of an error code.
+///
+/// The returned string must not be freed.
+#[unsafe(no_mangle)]
+pub extern "C" fn p2s_error_message(code: i32) -> *const c_char {
+    let message: &'static [u8] = match code {
+        P2S_OK => b"ok\0",
+        P2S_ERR_NULL_POINTER => b"null pointer argument\0",
+        P2S_ERR_PARSE => b"failed to parse PLY data\0",
+        P2S_ERR_INTERNAL => b"internal error\0",
+        _ => b"unknown error code\0",
+    };
+    message.as_ptr() as *const c_char
+}
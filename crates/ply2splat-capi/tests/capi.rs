@@ -0,0 +1,108 @@
+//! Exercises the C ABI the way a C caller would: through `extern "C"` declarations
+//! resolved against the exported symbols.
+
+use ply2splat_capi::{P2S_ERR_NULL_POINTER, P2S_ERR_PARSE, P2S_OK};
+use std::ffi::{CStr, c_char};
+
+unsafe extern "C" {
+    fn p2s_convert(
+        ply_ptr: *const u8,
+        ply_len: usize,
+        sort: bool,
+        out_ptr: *mut *mut u8,
+        out_len: *mut usize,
+    ) -> i32;
+    fn p2s_free(ptr: *mut u8, len: usize);
+    fn p2s_error_message(code: i32) -> *const c_char;
+}
+
+const PLY_FIXTURE: &[u8] = b"ply
+format ascii 1.0
+element vertex 2
+property float x
+property float y
+property float z
+property float f_dc_0
+property float f_dc_1
+property float f_dc_2
+property float opacity
+property float scale_0
+property float scale_1
+property float scale_2
+property float rot_0
+property float rot_1
+property float rot_2
+property float rot_3
+end_header
+0.0 0.0 0.0 0.5 0.5 0.5 1.0 0.1 0.1 0.1 1.0 0.0 0.0 0.0
+1.0 1.0 1.0 0.1 0.1 0.1 0.5 0.2 0.2 0.2 0.0 1.0 0.0 0.0
+";
+
+#[test]
+fn test_convert_and_free() {
+    let mut out_ptr: *mut u8 = std::ptr::null_mut();
+    let mut out_len: usize = 0;
+
+    let code = unsafe {
+        p2s_convert(
+            PLY_FIXTURE.as_ptr(),
+            PLY_FIXTURE.len(),
+            true,
+            &mut out_ptr,
+            &mut out_len,
+        )
+    };
+    assert_eq!(code, P2S_OK);
+    assert!(!out_ptr.is_null());
+    assert_eq!(out_len, 64); // 2 splats * 32 bytes
+
+    let output = unsafe { std::slice::from_raw_parts(out_ptr, out_len) };
+    let (expected, _) = ply2splat::convert(PLY_FIXTURE, true).unwrap();
+    assert_eq!(output, expected.as_slice());
+
+    unsafe { p2s_free(out_ptr, out_len) };
+}
+
+#[test]
+fn test_convert_invalid_input() {
+    let garbage = b"not a ply file";
+    let mut out_ptr: *mut u8 = std::ptr::dangling_mut();
+    let mut out_len: usize = 123;
+
+    let code = unsafe {
+        p2s_convert(
+            garbage.as_ptr(),
+            garbage.len(),
+            true,
+            &mut out_ptr,
+            &mut out_len,
+        )
+    };
+    assert_eq!(code, P2S_ERR_PARSE);
+    assert!(out_ptr.is_null());
+    assert_eq!(out_len, 0);
+
+    // Freeing the null result is a no-op.
+    unsafe { p2s_free(out_ptr, out_len) };
+}
+
+#[test]
+fn test_convert_null_pointer() {
+    let mut out_len: usize = 0;
+    let code = unsafe {
+        p2s_convert(
+            std::ptr::null(),
+            0,
+            true,
+            std::ptr::null_mut(),
+            &mut out_len,
+        )
+    };
+    assert_eq!(code, P2S_ERR_NULL_POINTER);
+}
+
+#[test]
+fn test_error_message() {
+    let message = unsafe { CStr::from_ptr(p2s_error_message(P2S_ERR_PARSE)) };
+    assert_eq!(message.to_str().unwrap(), "failed to parse PLY data");
+}
@@ -1,8 +1,81 @@
+import { mkdtempSync, statSync, writeFileSync } from "node:fs";
+import { tmpdir } from "node:os";
+import { join } from "node:path";
+
 import test from "ava";
 
-import { simpleFn } from "../index.js";
+import { convert, convertAsync, convertFile, simpleFn } from "../index.js";
+
+const PLY = Buffer.from(
+  [
+    "ply",
+    "format ascii 1.0",
+    "element vertex 2",
+    "property float x",
+    "property float y",
+    "property float z",
+    "property float f_dc_0",
+    "property float f_dc_1",
+    "property float f_dc_2",
+    "property float opacity",
+    "property float scale_0",
+    "property float scale_1",
+    "property float scale_2",
+    "property float rot_0",
+    "property float rot_1",
+    "property float rot_2",
+    "property float rot_3",
+    "end_header",
+    "0 0 0 0 0 0 1 0 0 0 1 0 0 0",
+    "1 1 1 0 0 0 -1 0 0 0 1 0 0 0",
+    "",
+  ].join("\n"),
+);
 
 test("sync function from native code", (t) => {
   const r = simpleFn();
   t.is(r, 1);
 });
+
+test("convert returns 32 bytes per splat", (t) => {
+  const result = convert(PLY);
+  t.is(result.count, 2);
+  t.is(result.data.length, result.count * 32);
+});
+
+test("convertAsync matches convert", async (t) => {
+  const result = await convertAsync(PLY);
+  t.is(result.count, 2);
+  t.deepEqual(result.data, convert(PLY).data);
+});
+
+test("convertAsync reports progress", async (t) => {
+  const calls: Array<[number, number]> = [];
+  await convertAsync(PLY, true, (processed, total) => calls.push([processed, total]));
+  // Progress is delivered through the event loop, so let pending calls run
+  await new Promise((resolve) => setImmediate(resolve));
+  t.deepEqual(calls[calls.length - 1], [2, 2]);
+});
+
+test("convert writes compact16 records", (t) => {
+  const result = convert(PLY, true, "compact16");
+  t.is(result.count, 2);
+  t.is(result.data.length, 2 * 20);
+  t.throws(() => convert(PLY, true, "f16" as never), { message: /Unknown format/ });
+});
+
+test("convertFile converts on disk", async (t) => {
+  const dir = mkdtempSync(join(tmpdir(), "ply2splat-"));
+  const input = join(dir, "input.ply");
+  const output = join(dir, "output.splat");
+  writeFileSync(input, PLY);
+
+  const count = await convertFile(input, output, { sort: false });
+  t.is(count, 2);
+  t.is(statSync(output).size, count * 32);
+  await t.throwsAsync(convertFile(join(dir, "missing.ply"), output), { message: /Failed to convert file/ });
+});
+
+test("convertAsync rejects invalid data", async (t) => {
+  await t.throwsAsync(convertAsync(Buffer.from("not a ply")), { message: /Failed to parse PLY data/ });
+});
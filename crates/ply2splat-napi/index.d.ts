@@ -18,11 +18,50 @@ export interface ConversionResult {
 /**
  * Convert PLY data to SPLAT format.
  *
+ * This blocks the calling thread until the conversion is done; prefer `convertAsync`
+ * for large inputs.
+ *
  * @param plyData - PLY file contents as a Buffer
  * @param sort - Whether to sort splats by importance (default: true)
+ * @param format - `"splat"` for 32-byte records (default) or `"compact16"` for 20-byte
+ *   records with half-float positions and scales
  * @returns Object containing the SPLAT data buffer and count
  */
-export declare function convert(plyData: Buffer, sort?: boolean | undefined | null): ConversionResult
+export declare function convert(plyData: Buffer, sort?: boolean | undefined | null, format?: 'splat' | 'compact16' | undefined | null): ConversionResult
+
+/**
+ * Convert PLY data to SPLAT format on the libuv worker pool.
+ *
+ * Parsing and conversion run off the main thread, so the event loop stays responsive.
+ * Parse failures reject the returned Promise.
+ *
+ * @param plyData - PLY file contents as a Buffer
+ * @param sort - Whether to sort splats by importance (default: true)
+ * @param onProgress - Called with `(processed, total)` as the conversion advances; the
+ *   last call reports `(total, total)` before sorting starts
+ * @param format - `"splat"` (default) or `"compact16"`, as for `convert`
+ * @returns Promise resolving to an object containing the SPLAT data buffer and count
+ */
+export declare function convertAsync(plyData: Buffer, sort?: boolean | undefined | null, onProgress?: ((processed: number, total: number) => void) | undefined | null, format?: 'splat' | 'compact16' | undefined | null): Promise<ConversionResult>
+
+/**
+ * Convert a PLY file to a SPLAT file on the libuv worker pool.
+ *
+ * Reading, conversion and writing all happen natively, so neither the PLY nor the SPLAT
+ * data is ever copied into a JS Buffer; prefer this over `convertAsync` for large files.
+ *
+ * @param inputPath - Path of the PLY file
+ * @param outputPath - Path of the SPLAT file to write
+ * @param options - Conversion options
+ * @returns Promise resolving to the number of splats converted
+ */
+export declare function convertFile(inputPath: string, outputPath: string, options?: ConvertFileOptions | undefined | null): Promise<number>
+
+/** Options of `convertFile`. */
+export interface ConvertFileOptions {
+  /** Whether to sort splats by importance (default: true) */
+  sort?: boolean
+}
 
 /**
  * Get the number of splats in a SPLAT data buffer.
@@ -574,5 +574,7 @@ if (!nativeBinding) {
 module.exports = nativeBinding
 module.exports.cli = nativeBinding.cli
 module.exports.convert = nativeBinding.convert
+module.exports.convertAsync = nativeBinding.convertAsync
+module.exports.convertFile = nativeBinding.convertFile
 module.exports.getSplatCount = nativeBinding.getSplatCount
 module.exports.simpleFn = nativeBinding.simpleFn
@@ -58,5 +58,7 @@ const {
 export default __napiModule.exports
 export const cli = __napiModule.exports.cli
 export const convert = __napiModule.exports.convert
+export const convertAsync = __napiModule.exports.convertAsync
+export const convertFile = __napiModule.exports.convertFile
 export const getSplatCount = __napiModule.exports.getSplatCount
 export const simpleFn = __napiModule.exports.simpleFn
@@ -110,5 +110,7 @@ const { instance: __napiInstance, module: __wasiModule, napiModule: __napiModule
 module.exports = __napiModule.exports
 module.exports.cli = __napiModule.exports.cli
 module.exports.convert = __napiModule.exports.convert
+module.exports.convertAsync = __napiModule.exports.convertAsync
+module.exports.convertFile = __napiModule.exports.convertFile
 module.exports.getSplatCount = __napiModule.exports.getSplatCount
 module.exports.simpleFn = __napiModule.exports.simpleFn
@@ -4,24 +4,69 @@
 //! offering better performance than the WASM version for large files.
 
 use napi::bindgen_prelude::*;
+use napi::threadsafe_function::{ThreadsafeFunction, ThreadsafeFunctionCallMode};
 use napi_derive::napi;
-use ply2splat::{load_ply_from_bytes, ply_to_splat, splats_to_bytes};
+use ply2splat::{
+    SplatFormat, load_ply_from_bytes, ply_to_splat, ply_to_splat_with_progress, splat_count,
+    splats_to_bytes_format,
+};
+
+/// JS callback receiving `(processed, total)` during a conversion.
+pub type ProgressCallback =
+    ThreadsafeFunction<FnArgs<(u32, u32)>, (), FnArgs<(u32, u32)>, Status, false>;
+
+/// Maps the `format` argument of the conversion functions to a record layout.
+fn parse_format(format: Option<&str>) -> Result<SplatFormat> {
+    match format {
+        None | Some("splat") => Ok(SplatFormat::Standard),
+        Some("compact16") => Ok(SplatFormat::Compact16),
+        Some(other) => Err(Error::from_reason(format!(
+            "Unknown format '{other}', expected 'splat' or 'compact16'"
+        ))),
+    }
+}
+
+/// Parses and converts PLY data, returning the SPLAT bytes and the splat count.
+fn convert_bytes(
+    ply_data: &[u8],
+    sort: bool,
+    format: SplatFormat,
+    on_progress: Option<&ProgressCallback>,
+) -> Result<(Vec<u8>, u32)> {
+    let ply_points = load_ply_from_bytes(ply_data)
+        .map_err(|e| Error::from_reason(format!("Failed to parse PLY data: {}", e)))?;
+
+    let count = ply_points.len() as u32;
+    let splats = match on_progress {
+        Some(callback) => ply_to_splat_with_progress(ply_points, sort, |done, total| {
+            callback.call(
+                (done as u32, total as u32).into(),
+                ThreadsafeFunctionCallMode::NonBlocking,
+            );
+        }),
+        None => ply_to_splat(ply_points, sort),
+    };
+    Ok((splats_to_bytes_format(&splats, format), count))
+}
 
 /// Convert PLY data to SPLAT format.
 ///
+/// This blocks the calling thread until the conversion is done; prefer `convertAsync`
+/// for large inputs.
+///
 /// @param plyData - PLY file contents as a Buffer
 /// @param sort - Whether to sort splats by importance (default: true)
+/// @param format - `"splat"` for 32-byte records (default) or `"compact16"` for 20-byte
+///   records with half-float positions and scales
 /// @returns Object containing the SPLAT data buffer and count
 #[napi]
-pub fn convert(ply_data: Buffer, sort: Option<bool>) -> Result<ConversionResult> {
-    let sort = sort.unwrap_or(true);
-
-    let ply_points = load_ply_from_bytes(&ply_data)
-        .map_err(|e| Error::from_reason(format!("Failed to parse PLY data: {}", e)))?;
-
-    let count = ply_points.len() as u32;
-    let splats = ply_to_splat(ply_points, sort);
-    let data = splats_to_bytes(&splats);
+pub fn convert(
+    ply_data: Buffer,
+    sort: Option<bool>,
+    #[napi(ts_arg_type = "'splat' | 'compact16'")] format: Option<String>,
+) -> Result<ConversionResult> {
+    let format = parse_format(format.as_deref())?;
+    let (data, count) = convert_bytes(&ply_data, sort.unwrap_or(true), format, None)?;
 
     Ok(ConversionResult {
         data: Buffer::from(data),
@@ -29,6 +74,113 @@ pub fn convert(ply_data: Buffer, sort: Option<bool>) -> Result<ConversionResult>
     })
 }
 
+/// Background task behind `convertAsync`.
+pub struct ConvertTask {
+    ply_data: Buffer,
+    sort: bool,
+    format: SplatFormat,
+    on_progress: Option<ProgressCallback>,
+}
+
+impl Task for ConvertTask {
+    type Output = (Vec<u8>, u32);
+    type JsValue = ConversionResult;
+
+    fn compute(&mut self) -> Result<Self::Output> {
+        convert_bytes(
+            &self.ply_data,
+            self.sort,
+            self.format,
+            self.on_progress.as_ref(),
+        )
+    }
+
+    fn resolve(&mut self, _env: Env, (data, count): Self::Output) -> Result<Self::JsValue> {
+        Ok(ConversionResult {
+            data: Buffer::from(data),
+            count,
+        })
+    }
+}
+
+/// Convert PLY data to SPLAT format on the libuv worker pool.
+///
+/// Parsing and conversion run off the main thread, so the event loop stays responsive.
+/// Parse failures reject the returned Promise.
+///
+/// @param plyData - PLY file contents as a Buffer
+/// @param sort - Whether to sort splats by importance (default: true)
+/// @param onProgress - Called with `(processed, total)` as the conversion advances; the
+///   last call reports `(total, total)` before sorting starts
+/// @param format - `"splat"` (default) or `"compact16"`, as for `convert`
+/// @returns Promise resolving to an object containing the SPLAT data buffer and count
+#[napi]
+pub fn convert_async(
+    ply_data: Buffer,
+    sort: Option<bool>,
+    #[napi(ts_arg_type = "(processed: number, total: number) => void")] on_progress: Option<
+        ProgressCallback,
+    >,
+    #[napi(ts_arg_type = "'splat' | 'compact16'")] format: Option<String>,
+) -> Result<AsyncTask<ConvertTask>> {
+    Ok(AsyncTask::new(ConvertTask {
+        ply_data,
+        sort: sort.unwrap_or(true),
+        format: parse_format(format.as_deref())?,
+        on_progress,
+    }))
+}
+
+/// Options of `convertFile`.
+#[napi(object)]
+pub struct ConvertFileOptions {
+    /// Whether to sort splats by importance (default: true)
+    pub sort: Option<bool>,
+}
+
+/// Background task behind `convertFile`.
+pub struct ConvertFileTask {
+    input_path: String,
+    output_path: String,
+    sort: bool,
+}
+
+impl Task for ConvertFileTask {
+    type Output = usize;
+    type JsValue = u32;
+
+    fn compute(&mut self) -> Result<Self::Output> {
+        ply2splat::convert_file(&self.input_path, &self.output_path, self.sort)
+            .map_err(|e| Error::from_reason(format!("Failed to convert file: {:#}", e)))
+    }
+
+    fn resolve(&mut self, _env: Env, count: Self::Output) -> Result<Self::JsValue> {
+        Ok(count as u32)
+    }
+}
+
+/// Convert a PLY file to a SPLAT file on the libuv worker pool.
+///
+/// Reading, conversion and writing all happen natively, so neither the PLY nor the SPLAT
+/// data is ever copied into a JS Buffer; prefer this over `convertAsync` for large files.
+///
+/// @param inputPath - Path of the PLY file
+/// @param outputPath - Path of the SPLAT file to write
+/// @param options - Conversion options
+/// @returns Promise resolving to the number of splats converted
+#[napi]
+pub fn convert_file(
+    input_path: String,
+    output_path: String,
+    options: Option<ConvertFileOptions>,
+) -> AsyncTask<ConvertFileTask> {
+    AsyncTask::new(ConvertFileTask {
+        input_path,
+        output_path,
+        sort: options.and_then(|o| o.sort).unwrap_or(true),
+    })
+}
+
 #[napi]
 pub fn simple_fn() -> u32 {
     1
@@ -38,6 +190,12 @@ pub fn simple_fn() -> u32 {
 #[napi(object)]
 pub struct ConversionResult {
     /// The converted SPLAT data
+    ///
+    /// The buffer is created as an external buffer that takes ownership of the converted
+    /// bytes without copying them. Runtimes that disallow external buffers, including the
+    /// emnapi-based WASM build, make napi fall back to a buffer holding a copy, so there
+    /// the data is copied once. Either way `data.length` is `count * 32` (or `count * 20`
+    /// for `compact16`).
     pub data: Buffer,
     /// Number of splats in the result
     pub count: u32,
@@ -49,13 +207,8 @@ pub struct ConversionResult {
 /// @returns Number of splats in the data
 #[napi]
 pub fn get_splat_count(splat_data: Buffer) -> Result<u32> {
-    if !splat_data.len().is_multiple_of(32) {
-        return Err(Error::from_reason(format!(
-            "Invalid SPLAT data: size {} is not a multiple of 32 bytes",
-            splat_data.len()
-        )));
-    }
-    Ok((splat_data.len() / 32) as u32)
+    let count = splat_count(&splat_data).map_err(|e| Error::from_reason(e.to_string()))?;
+    Ok(count as u32)
 }
 
 /// Run the ply2splat CLI directly.
@@ -3,11 +3,30 @@
 //! This module exposes the core functionality of the ply2splat library to Python
 //! via PyO3, allowing Python users to convert PLY files to SPLAT format.
 
-use ply2splat_lib::{SplatPoint, load_ply, ply_to_splat, save_splat};
-use pyo3::exceptions::PyIOError;
+use ply2splat_lib::{
+    ConvertOptions, PlyGaussian, SplatFormat, SplatPoint, compute_stats, load_ply,
+    load_ply_from_bytes, ply_to_splat, ply_to_splat_with_normals, save_normals, save_splat,
+    splats_from_bytes, splats_from_bytes_format, splats_to_bytes_format,
+};
+use pyo3::exceptions::{PyIOError, PyValueError};
 use pyo3::prelude::*;
+use pyo3::types::PyType;
+use rayon::prelude::*;
+use std::collections::HashMap;
 use std::fs::File;
 use std::io::{BufReader, Read};
+use std::path::{Path, PathBuf};
+
+/// Maps the `format` argument of `to_bytes` and `from_bytes` to a record layout.
+fn parse_format(format: &str) -> PyResult<SplatFormat> {
+    match format {
+        "splat" => Ok(SplatFormat::Standard),
+        "compact16" => Ok(SplatFormat::Compact16),
+        other => Err(PyValueError::new_err(format!(
+            "unknown format '{other}', expected 'splat' or 'compact16'"
+        ))),
+    }
+}
 
 /// A single Gaussian Splat with position, scale, color, and rotation.
 ///
@@ -28,10 +47,23 @@ pub struct Splat {
     /// Rotation quaternion encoded as (r0, r1, r2, r3), values 0-255
     #[pyo3(get)]
     pub rotation: (u8, u8, u8, u8),
+    /// Normal (nx, ny, nz), or None when the data was loaded without normals
+    #[pyo3(get)]
+    pub normal: Option<(f32, f32, f32)>,
 }
 
 #[pymethods]
 impl Splat {
+    /// Move this splat. Splats are copies; store them back with `data[i] = splat`.
+    fn set_position(&mut self, position: (f32, f32, f32)) {
+        self.position = position;
+    }
+
+    /// Recolor this splat. Splats are copies; store them back with `data[i] = splat`.
+    fn set_color(&mut self, color: (u8, u8, u8, u8)) {
+        self.color = color;
+    }
+
     fn __repr__(&self) -> String {
         format!(
             "Splat(position={:?}, scale={:?}, color={:?}, rotation={:?})",
@@ -47,6 +79,58 @@ impl From<&SplatPoint> for Splat {
             scale: (sp.scale[0], sp.scale[1], sp.scale[2]),
             color: (sp.color[0], sp.color[1], sp.color[2], sp.color[3]),
             rotation: (sp.rot[0], sp.rot[1], sp.rot[2], sp.rot[3]),
+            normal: None,
+        }
+    }
+}
+
+/// Summary statistics of a collection of splats.
+#[pyclass]
+#[derive(Clone)]
+pub struct SplatStats {
+    /// Number of splats
+    #[pyo3(get)]
+    pub count: usize,
+    /// Minimum corner (x, y, z) of the bounding box of the splat centers
+    #[pyo3(get)]
+    pub min: (f32, f32, f32),
+    /// Maximum corner (x, y, z) of the bounding box
+    #[pyo3(get)]
+    pub max: (f32, f32, f32),
+    /// Mean scale (x, y, z)
+    #[pyo3(get)]
+    pub mean_scale: (f32, f32, f32),
+    /// Largest scale along any axis
+    #[pyo3(get)]
+    pub max_scale: f32,
+    /// Mean opacity in [0, 1]
+    #[pyo3(get)]
+    pub mean_opacity: f32,
+    /// Splat counts for ten opacity buckets of width 0.1
+    #[pyo3(get)]
+    pub opacity_histogram: Vec<usize>,
+}
+
+#[pymethods]
+impl SplatStats {
+    fn __repr__(&self) -> String {
+        format!(
+            "SplatStats(count={}, min={:?}, max={:?}, mean_scale={:?}, max_scale={}, mean_opacity={})",
+            self.count, self.min, self.max, self.mean_scale, self.max_scale, self.mean_opacity
+        )
+    }
+}
+
+impl From<ply2splat_lib::SplatStats> for SplatStats {
+    fn from(s: ply2splat_lib::SplatStats) -> Self {
+        SplatStats {
+            count: s.count,
+            min: (s.min[0], s.min[1], s.min[2]),
+            max: (s.max[0], s.max[1], s.max[2]),
+            mean_scale: (s.mean_scale[0], s.mean_scale[1], s.mean_scale[2]),
+            max_scale: s.max_scale,
+            mean_opacity: s.mean_opacity,
+            opacity_histogram: s.opacity_histogram.to_vec(),
         }
     }
 }
@@ -58,17 +142,36 @@ impl From<&SplatPoint> for Splat {
 #[pyclass]
 pub struct SplatData {
     splats: Vec<SplatPoint>,
+    /// Per-splat normals, in the same order as `splats`
+    normals: Option<Vec<[f32; 3]>>,
 }
 
-#[pymethods]
 impl SplatData {
-    /// Get the number of splats.
-    fn __len__(&self) -> usize {
-        self.splats.len()
+    fn new(splats: Vec<SplatPoint>) -> Self {
+        SplatData {
+            splats,
+            normals: None,
+        }
     }
 
-    /// Get a splat by index.
-    fn __getitem__(&self, index: isize) -> PyResult<Splat> {
+    /// Converts Gaussians, keeping their normals if `normals` is set.
+    fn convert(ply_data: Vec<PlyGaussian>, sort: bool, normals: bool) -> Self {
+        if !normals {
+            return Self::new(ply_to_splat(ply_data, sort));
+        }
+        let options = ConvertOptions {
+            sort,
+            ..Default::default()
+        };
+        let (splats, normals) = ply_to_splat_with_normals(ply_data, &options);
+        SplatData {
+            splats,
+            normals: Some(normals),
+        }
+    }
+
+    /// Resolves a possibly negative Python index.
+    fn index(&self, index: isize) -> PyResult<usize> {
         let len = self.splats.len() as isize;
         let idx = if index < 0 { len + index } else { index };
         if idx < 0 || idx >= len {
@@ -76,7 +179,63 @@ impl SplatData {
                 "index out of range",
             ));
         }
-        Ok(Splat::from(&self.splats[idx as usize]))
+        Ok(idx as usize)
+    }
+
+    fn splat(&self, index: usize) -> Splat {
+        let mut splat = Splat::from(&self.splats[index]);
+        splat.normal = self.normals.as_ref().map(|n| n[index].into());
+        splat
+    }
+}
+
+#[pymethods]
+impl SplatData {
+    /// Get the number of splats.
+    fn __len__(&self) -> usize {
+        self.splats.len()
+    }
+
+    /// Get a splat by index.
+    fn __getitem__(&self, index: isize) -> PyResult<Splat> {
+        Ok(self.splat(self.index(index)?))
+    }
+
+    /// Replace a splat by index, e.g. with one edited through `Splat.set_color`.
+    ///
+    /// The normal, if any, is kept unless the new splat carries one.
+    fn __setitem__(&mut self, index: isize, splat: Splat) -> PyResult<()> {
+        let idx = self.index(index)?;
+        let (x, y, z) = splat.position;
+        let (sx, sy, sz) = splat.scale;
+        let (r, g, b, a) = splat.color;
+        let (r0, r1, r2, r3) = splat.rotation;
+        self.splats[idx] = SplatPoint {
+            pos: [x, y, z],
+            scale: [sx, sy, sz],
+            color: [r, g, b, a],
+            rot: [r0, r1, r2, r3],
+        };
+        if let (Some(normals), Some((nx, ny, nz))) = (&mut self.normals, splat.normal) {
+            normals[idx] = [nx, ny, nz];
+        }
+        Ok(())
+    }
+
+    /// Move the splat at `index` to `position` (x, y, z).
+    fn set_position(&mut self, index: isize, position: (f32, f32, f32)) -> PyResult<()> {
+        let idx = self.index(index)?;
+        let (x, y, z) = position;
+        self.splats[idx].pos = [x, y, z];
+        Ok(())
+    }
+
+    /// Recolor the splat at `index` with `color` (R, G, B, A), values 0-255.
+    fn set_color(&mut self, index: isize, color: (u8, u8, u8, u8)) -> PyResult<()> {
+        let idx = self.index(index)?;
+        let (r, g, b, a) = color;
+        self.splats[idx].color = [r, g, b, a];
+        Ok(())
     }
 
     /// Iterate over all splats.
@@ -89,12 +248,52 @@ impl SplatData {
 
     /// Get all splats as a list.
     fn to_list(&self) -> Vec<Splat> {
-        self.splats.iter().map(Splat::from).collect()
+        (0..self.splats.len()).map(|i| self.splat(i)).collect()
+    }
+
+    /// Per-splat normals as (nx, ny, nz) tuples, or None when loaded without normals.
+    #[getter]
+    fn normals(&self) -> Option<Vec<(f32, f32, f32)>> {
+        self.normals
+            .as_ref()
+            .map(|normals| normals.iter().map(|&n| n.into()).collect())
+    }
+
+    /// Save the normals to a sidecar file of little-endian f32 triples in splat order.
+    fn save_normals(&self, path: &str) -> PyResult<()> {
+        let normals = self
+            .normals
+            .as_ref()
+            .ok_or_else(|| PyValueError::new_err("splat data was loaded without normals"))?;
+        save_normals(path, normals).map_err(|e| PyIOError::new_err(e.to_string()))
     }
 
     /// Get the raw bytes representation of all splats.
-    fn to_bytes(&self) -> Vec<u8> {
-        bytemuck::cast_slice(&self.splats).to_vec()
+    ///
+    /// `format` is "splat" for 32-byte records or "compact16" for 20-byte records with
+    /// half-float positions and scales.
+    #[pyo3(signature = (format="splat"))]
+    fn to_bytes(&self, format: &str) -> PyResult<Vec<u8>> {
+        Ok(splats_to_bytes_format(&self.splats, parse_format(format)?))
+    }
+
+    /// Compute bounding box, scale and opacity statistics.
+    fn stats(&self) -> SplatStats {
+        compute_stats(&self.splats).into()
+    }
+
+    /// Save all splats to a SPLAT file.
+    fn save(&self, path: &str) -> PyResult<()> {
+        save_splat(path, &self.splats).map_err(|e| PyIOError::new_err(e.to_string()))
+    }
+
+    /// Create splat data from raw SPLAT bytes, as returned by `to_bytes` with the same `format`.
+    #[classmethod]
+    #[pyo3(signature = (data, format="splat"))]
+    fn from_bytes(_cls: &Bound<'_, PyType>, data: &[u8], format: &str) -> PyResult<Self> {
+        let splats = splats_from_bytes_format(data, parse_format(format)?)
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Ok(SplatData::new(splats))
     }
 
     fn __repr__(&self) -> String {
@@ -120,7 +319,7 @@ impl SplatIterator {
         let data = slf.data.borrow(py);
         let current_index = slf.index;
         if current_index < data.splats.len() {
-            let splat = Splat::from(&data.splats[current_index]);
+            let splat = data.splat(current_index);
             drop(data); // Release the borrow before mutating
             slf.index += 1;
             Some(splat)
@@ -152,6 +351,84 @@ fn convert(input_path: &str, output_path: &str, sort: bool) -> PyResult<usize> {
     Ok(count)
 }
 
+/// Converts every input to `<output_dir>/<file stem>.splat`, several files at a time.
+///
+/// A failing file does not stop the others; its entry holds the error message instead.
+/// Inputs sharing a file stem would write the same output, so none of them is converted.
+fn convert_files(
+    input_paths: &[String],
+    output_dir: &Path,
+    sort: bool,
+) -> Vec<Result<usize, String>> {
+    if let Err(e) = std::fs::create_dir_all(output_dir) {
+        let message = format!("Failed to create output directory: {e}");
+        return vec![Err(message); input_paths.len()];
+    }
+    let outputs: Vec<Option<PathBuf>> = input_paths
+        .iter()
+        .map(|input| {
+            let stem = Path::new(input).file_stem()?;
+            Some(output_dir.join(stem).with_extension("splat"))
+        })
+        .collect();
+    let mut by_output: HashMap<&Path, Vec<&str>> = HashMap::new();
+    for (input, output) in input_paths.iter().zip(&outputs) {
+        if let Some(output) = output {
+            by_output.entry(output).or_default().push(input);
+        }
+    }
+    input_paths
+        .par_iter()
+        .zip(&outputs)
+        .map(|(input, output)| {
+            let output = output
+                .as_deref()
+                .ok_or_else(|| format!("{input}: not a file path"))?;
+            let sharing = &by_output[output];
+            if sharing.len() > 1 {
+                return Err(format!(
+                    "{input}: output {} is shared with other inputs: {}",
+                    output.display(),
+                    sharing.join(", ")
+                ));
+            }
+            ply2splat_lib::convert_file(Path::new(input), output, sort)
+                .map_err(|e| format!("{input}: {e:#}"))
+        })
+        .collect()
+}
+
+/// Convert many PLY files into a directory, in parallel and without holding the GIL.
+///
+/// Each input is written to `<output_dir>/<file stem>.splat`; inputs sharing a file stem
+/// fail instead of overwriting each other. The output directory is created if needed.
+///
+/// Args:
+///     input_paths: Paths of the input PLY files
+///     output_dir: Directory for the output SPLAT files
+///     sort: Whether to sort splats by importance (default: True)
+///
+/// Returns:
+///     One entry per input, in order: the number of splats converted, or an IOError
+///     instance (not raised) describing why that file failed
+#[pyfunction]
+#[pyo3(signature = (input_paths, output_dir, sort=true))]
+fn convert_batch(
+    py: Python<'_>,
+    input_paths: Vec<String>,
+    output_dir: &str,
+    sort: bool,
+) -> PyResult<Vec<PyObject>> {
+    let results = py.allow_threads(|| convert_files(&input_paths, Path::new(output_dir), sort));
+    results
+        .into_iter()
+        .map(|result| match result {
+            Ok(count) => Ok(count.into_pyobject(py)?.into_any().unbind()),
+            Err(message) => Ok(PyIOError::new_err(message).into_value(py).into_any()),
+        })
+        .collect()
+}
+
 /// Load a PLY file and return splat data as bytes.
 ///
 /// This function loads a PLY file, converts it to SPLAT format, and returns
@@ -186,6 +463,7 @@ fn load_and_convert(input_path: &str, sort: bool) -> PyResult<(Vec<u8>, usize)>
 /// Args:
 ///     input_path: Path to the input PLY file
 ///     sort: Whether to sort splats by importance (default: True)
+///     normals: Whether to keep the per-splat normals (nx, ny, nz) (default: False)
 ///
 /// Returns:
 ///     A SplatData object containing all splats
@@ -193,11 +471,10 @@ fn load_and_convert(input_path: &str, sort: bool) -> PyResult<(Vec<u8>, usize)>
 /// Raises:
 ///     IOError: If the input file cannot be read
 #[pyfunction]
-#[pyo3(signature = (input_path, sort=true))]
-fn load_ply_file(input_path: &str, sort: bool) -> PyResult<SplatData> {
+#[pyo3(signature = (input_path, sort=true, normals=false))]
+fn load_ply_file(input_path: &str, sort: bool, normals: bool) -> PyResult<SplatData> {
     let ply_data = load_ply(input_path).map_err(|e| PyIOError::new_err(e.to_string()))?;
-    let splats = ply_to_splat(ply_data, sort);
-    Ok(SplatData { splats })
+    Ok(SplatData::convert(ply_data, sort, normals))
 }
 
 /// Load a SPLAT file and return structured splat data.
@@ -222,15 +499,46 @@ fn load_splat_file(input_path: &str) -> PyResult<SplatData> {
         .read_to_end(&mut bytes)
         .map_err(|e| PyIOError::new_err(e.to_string()))?;
 
-    if bytes.len() % 32 != 0 {
-        return Err(PyIOError::new_err(format!(
-            "Invalid SPLAT file: size {} is not a multiple of 32 bytes",
-            bytes.len()
-        )));
-    }
+    let splats = splats_from_bytes(&bytes).map_err(|e| PyIOError::new_err(e.to_string()))?;
+    Ok(SplatData::new(splats))
+}
 
-    let splats: Vec<SplatPoint> = bytemuck::cast_slice(&bytes).to_vec();
-    Ok(SplatData { splats })
+/// Convert in-memory PLY data to the compact SPLAT binary format.
+///
+/// Args:
+///     ply_bytes: Contents of a Gaussian Splatting PLY file
+///     sort: Whether to sort splats by importance (default: True)
+///
+/// Returns:
+///     A tuple of (bytes, count) where bytes is the raw SPLAT data and count
+///     is the number of splats
+///
+/// Raises:
+///     ValueError: If the data is not a valid Gaussian Splatting PLY
+#[pyfunction]
+#[pyo3(signature = (ply_bytes, sort=true))]
+fn convert_bytes(ply_bytes: &[u8], sort: bool) -> PyResult<(Vec<u8>, usize)> {
+    ply2splat_lib::convert(ply_bytes, sort).map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+/// Load in-memory PLY data and return structured splat data.
+///
+/// Args:
+///     ply_bytes: Contents of a Gaussian Splatting PLY file
+///     sort: Whether to sort splats by importance (default: True)
+///     normals: Whether to keep the per-splat normals (nx, ny, nz) (default: False)
+///
+/// Returns:
+///     A SplatData object containing all splats
+///
+/// Raises:
+///     ValueError: If the data is not a valid Gaussian Splatting PLY
+#[pyfunction]
+#[pyo3(signature = (ply_bytes, sort=true, normals=false))]
+fn load_ply_bytes(ply_bytes: &[u8], sort: bool, normals: bool) -> PyResult<SplatData> {
+    let ply_data =
+        load_ply_from_bytes(ply_bytes).map_err(|e| PyValueError::new_err(e.to_string()))?;
+    Ok(SplatData::convert(ply_data, sort, normals))
 }
 
 /// Run the ply2splat CLI.
@@ -247,10 +555,103 @@ fn main(py: Python<'_>) -> PyResult<()> {
 fn ply2splat(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<Splat>()?;
     m.add_class::<SplatData>()?;
+    m.add_class::<SplatStats>()?;
     m.add_function(wrap_pyfunction!(convert, m)?)?;
+    m.add_function(wrap_pyfunction!(convert_batch, m)?)?;
     m.add_function(wrap_pyfunction!(load_and_convert, m)?)?;
     m.add_function(wrap_pyfunction!(load_ply_file, m)?)?;
     m.add_function(wrap_pyfunction!(load_splat_file, m)?)?;
+    m.add_function(wrap_pyfunction!(convert_bytes, m)?)?;
+    m.add_function(wrap_pyfunction!(load_ply_bytes, m)?)?;
     m.add_function(wrap_pyfunction!(main, m)?)?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PLY: &[u8] = b"ply
+format ascii 1.0
+element vertex 2
+property float x
+property float y
+property float z
+property float f_dc_0
+property float f_dc_1
+property float f_dc_2
+property float opacity
+property float scale_0
+property float scale_1
+property float scale_2
+property float rot_0
+property float rot_1
+property float rot_2
+property float rot_3
+end_header
+0 0 0 0 0 0 1 0 0 0 1 0 0 0
+1 2 3 0.5 0 0 -1 -1 -1 -1 1 0 0 0
+";
+
+    #[test]
+    fn test_in_memory_round_trip() {
+        let (bytes, count) = ply2splat_lib::convert(PLY, true).unwrap();
+        assert_eq!(count, 2);
+        assert_eq!(bytes.len(), 64);
+
+        let splats = ply_to_splat(load_ply_from_bytes(PLY).unwrap(), true);
+        assert_eq!(bytemuck::cast_slice::<SplatPoint, u8>(&splats), &bytes[..]);
+
+        let back = splats_from_bytes(&bytes).unwrap();
+        assert_eq!(bytemuck::cast_slice::<SplatPoint, u8>(&back), &bytes[..]);
+
+        assert!(splats_from_bytes(&bytes[..40]).is_err());
+        assert!(load_ply_from_bytes(b"not a ply").is_err());
+    }
+
+    #[test]
+    fn test_convert_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let inputs: Vec<String> = ["a", "b", "missing"]
+            .iter()
+            .map(|name| {
+                let path = dir.path().join(format!("{name}.ply"));
+                if *name != "missing" {
+                    std::fs::write(&path, PLY).unwrap();
+                }
+                path.to_string_lossy().into_owned()
+            })
+            .collect();
+        let out = dir.path().join("out");
+
+        let results = convert_files(&inputs, &out, true);
+        assert_eq!(results[0], Ok(2));
+        assert_eq!(results[1], Ok(2));
+        assert!(results[2].as_ref().unwrap_err().contains("missing.ply"));
+        assert_eq!(std::fs::read(out.join("a.splat")).unwrap().len(), 64);
+        assert_eq!(std::fs::read(out.join("b.splat")).unwrap().len(), 64);
+    }
+
+    #[test]
+    fn test_convert_files_shared_stem() {
+        let dir = tempfile::tempdir().unwrap();
+        let inputs: Vec<String> = ["a/scene.ply", "b/scene.ply", "c/other.ply"]
+            .iter()
+            .map(|name| {
+                let path = dir.path().join(name);
+                std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+                std::fs::write(&path, PLY).unwrap();
+                path.to_string_lossy().into_owned()
+            })
+            .collect();
+        let out = dir.path().join("out");
+
+        let results = convert_files(&inputs, &out, true);
+        for result in &results[..2] {
+            let message = result.as_ref().unwrap_err();
+            assert!(message.contains(&inputs[0]) && message.contains(&inputs[1]));
+        }
+        assert_eq!(results[2], Ok(2));
+        assert!(!out.join("scene.splat").exists());
+    }
+}
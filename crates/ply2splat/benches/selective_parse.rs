@@ -0,0 +1,63 @@
+//! Compares the generic `ply-rs` parse against the selective binary reader on a
+//! property-heavy file (60 float properties per vertex, 14 of them used).
+//!
+//! Run with `cargo bench -p ply2splat --bench selective_parse`.
+
+use ply2splat::{load_ply_from_bytes, load_ply_from_bytes_selective};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const VERTICES: usize = 200_000;
+const USED: [&str; 14] = [
+    "x", "y", "z", "f_dc_0", "f_dc_1", "f_dc_2", "opacity", "scale_0", "scale_1", "scale_2",
+    "rot_0", "rot_1", "rot_2", "rot_3",
+];
+
+fn build_ply() -> Vec<u8> {
+    let mut header = format!("ply\nformat binary_little_endian 1.0\nelement vertex {VERTICES}\n");
+    for name in USED {
+        header += &format!("property float {name}\n");
+    }
+    for i in 0..46 {
+        header += &format!("property float f_rest_{i}\n");
+    }
+    header += "end_header\n";
+
+    let mut data = header.into_bytes();
+    for i in 0..VERTICES * 60 {
+        data.extend_from_slice(&((i % 97) as f32 * 0.01).to_le_bytes());
+    }
+    data
+}
+
+fn time<F: FnMut()>(mut f: F) -> Duration {
+    let runs = 5;
+    let start = Instant::now();
+    for _ in 0..runs {
+        f();
+    }
+    start.elapsed() / runs
+}
+
+fn main() {
+    let data = build_ply();
+    println!(
+        "{} vertices, 60 properties, {:.1} MB",
+        VERTICES,
+        data.len() as f64 / 1e6
+    );
+
+    let full = time(|| {
+        black_box(load_ply_from_bytes(&data).unwrap());
+    });
+    let selective = time(|| {
+        black_box(load_ply_from_bytes_selective(&data).unwrap());
+    });
+
+    println!("ply-rs:    {:.3}s", full.as_secs_f64());
+    println!("selective: {:.3}s", selective.as_secs_f64());
+    println!(
+        "speedup:   {:.1}x",
+        full.as_secs_f64() / selective.as_secs_f64()
+    );
+}
@@ -0,0 +1,121 @@
+//! How stored scales and opacities map to linear sizes and alphas.
+//!
+//! The reference exporter stores log-scales and logit opacities, which the conversion turns
+//! into sizes with `exp` and alphas with the sigmoid. Some exporters write the activated
+//! values instead; applying `exp` to those blows every splat up, so such files have to be
+//! read with `ActivationMode::PreActivated`.
+
+use crate::PlyGaussian;
+
+/// Whether `PlyGaussian` scales and opacities still need their activation functions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ActivationMode {
+    /// Log-scales and logit opacities, activated with `exp` and the sigmoid.
+    #[default]
+    Raw,
+    /// Linear scales and opacities in `[0, 1]`, used as they are.
+    PreActivated,
+}
+
+impl ActivationMode {
+    /// Linear scales of `p`.
+    pub fn scales(self, p: &PlyGaussian) -> [f32; 3] {
+        let scales = [p.scale_0, p.scale_1, p.scale_2];
+        match self {
+            Self::Raw => scales.map(f32::exp),
+            Self::PreActivated => scales,
+        }
+    }
+
+    /// Alpha of `p`, in `[0, 1]`.
+    pub fn opacity(self, p: &PlyGaussian) -> f32 {
+        match self {
+            Self::Raw => 1.0 / (1.0 + (-p.opacity).exp()),
+            Self::PreActivated => p.opacity,
+        }
+        .clamp(0.0, 1.0)
+    }
+
+    /// Volume of the scale ellipsoid up to a constant factor, as used for importance sorting.
+    pub(crate) fn volume(self, p: &PlyGaussian) -> f32 {
+        match self {
+            Self::Raw => (p.scale_0 + p.scale_1 + p.scale_2).exp(),
+            Self::PreActivated => p.scale_0 * p.scale_1 * p.scale_2,
+        }
+    }
+}
+
+/// Heuristic for files read with `ActivationMode::Raw` that were written pre-activated.
+///
+/// Log-scales of real captures are mostly negative, so a non-empty scene whose scales are
+/// all in `(0, 1)` most likely stores linear scales.
+pub fn looks_pre_activated(points: &[PlyGaussian]) -> bool {
+    !points.is_empty()
+        && points.iter().all(|p| {
+            [p.scale_0, p.scale_1, p.scale_2]
+                .iter()
+                .all(|s| *s > 0.0 && *s < 1.0)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ConvertOptions, SplatFilter, SplatPoint, Transform, ply_to_splat_with_options};
+
+    fn linear() -> PlyGaussian {
+        PlyGaussian {
+            scale_0: 0.5,
+            scale_1: 0.25,
+            scale_2: 0.125,
+            opacity: 0.6,
+            rot_0: 1.0,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_pre_activated_used_as_is() {
+        let options = ConvertOptions {
+            activation: ActivationMode::PreActivated,
+            ..Default::default()
+        };
+        let (splat, key) = SplatPoint::from_ply_with_options(&linear(), &options);
+        assert_eq!(splat.scale, [0.5, 0.25, 0.125]);
+        assert_eq!(splat.color[3], (0.6f32 * 255.0) as u8);
+        assert_eq!(key, -(0.5 * 0.25 * 0.125 * 0.6));
+
+        let (raw, _) = SplatPoint::from_ply(&linear());
+        assert_eq!(raw.scale, [0.5f32.exp(), 0.25f32.exp(), 0.125f32.exp()]);
+        assert_ne!(raw.color[3], splat.color[3]);
+
+        // Transforms and filters work on the linear values as well
+        let options = ConvertOptions {
+            transform: Transform {
+                scale: 2.0,
+                ..Transform::IDENTITY
+            },
+            filter: SplatFilter {
+                max_scale: 0.4,
+                ..Default::default()
+            },
+            ..options
+        };
+        let mut small = linear();
+        small.scale_0 = 0.25;
+        let splats = ply_to_splat_with_options(vec![linear(), small], &options);
+        assert_eq!(splats.len(), 1);
+        assert_eq!(splats[0].scale, [0.5, 0.5, 0.25]);
+    }
+
+    #[test]
+    fn test_looks_pre_activated() {
+        assert!(looks_pre_activated(&[linear()]));
+        let log = PlyGaussian {
+            scale_0: -4.0,
+            ..linear()
+        };
+        assert!(!looks_pre_activated(&[linear(), log]));
+        assert!(!looks_pre_activated(&[]));
+    }
+}
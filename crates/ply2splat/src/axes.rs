@@ -0,0 +1,140 @@
+//! Axis conventions of the target viewer.
+//!
+//! Both conventions are mirrorings, so besides positions the Gaussian's orientation has to be
+//! remapped: for a reflection `M` the covariance `R S² Rᵀ` becomes `M R S² Rᵀ M`, which is
+//! again a rotation times a diagonal scale once the quaternion and, for swapped axes, the
+//! scales are remapped as well.
+
+use crate::PlyGaussian;
+
+/// Axis convention applied to the converted scene.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AxisConvention {
+    /// Keep the axes of the input.
+    #[default]
+    Unchanged,
+    /// Swap the Y and Z axes, e.g. between Y-up and Z-up viewers: `(x, y, z) -> (x, z, y)`.
+    FlipYz,
+    /// Negate the Z axis to switch between right- and left-handed coordinates:
+    /// `(x, y, z) -> (x, y, -z)`.
+    FlipHandedness,
+}
+
+impl AxisConvention {
+    /// Remaps a Gaussian's position, normal, orientation and scales into this convention.
+    ///
+    /// The quaternion is stored as (w, x, y, z) in `rot_0..rot_3`. Mirroring a rotation by a
+    /// reflection `M` keeps `w` and maps the axis part `v` to `-M v`.
+    pub fn apply(self, mut p: PlyGaussian) -> PlyGaussian {
+        match self {
+            Self::Unchanged => {}
+            Self::FlipYz => {
+                std::mem::swap(&mut p.y, &mut p.z);
+                std::mem::swap(&mut p.ny, &mut p.nz);
+                std::mem::swap(&mut p.scale_1, &mut p.scale_2);
+                (p.rot_1, p.rot_2, p.rot_3) = (-p.rot_1, -p.rot_3, -p.rot_2);
+            }
+            Self::FlipHandedness => {
+                p.z = -p.z;
+                p.nz = -p.nz;
+                (p.rot_1, p.rot_2) = (-p.rot_1, -p.rot_2);
+            }
+        }
+        p
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ConvertOptions, ply_to_splat_with_options};
+
+    type Mat3 = [[f32; 3]; 3];
+
+    /// Covariance `R S² Rᵀ` of a Gaussian.
+    fn covariance(p: &PlyGaussian) -> Mat3 {
+        let len =
+            (p.rot_0 * p.rot_0 + p.rot_1 * p.rot_1 + p.rot_2 * p.rot_2 + p.rot_3 * p.rot_3).sqrt();
+        let [w, x, y, z] = [p.rot_0, p.rot_1, p.rot_2, p.rot_3].map(|c| c / len);
+        let r = [
+            [
+                1.0 - 2.0 * (y * y + z * z),
+                2.0 * (x * y - w * z),
+                2.0 * (x * z + w * y),
+            ],
+            [
+                2.0 * (x * y + w * z),
+                1.0 - 2.0 * (x * x + z * z),
+                2.0 * (y * z - w * x),
+            ],
+            [
+                2.0 * (x * z - w * y),
+                2.0 * (y * z + w * x),
+                1.0 - 2.0 * (x * x + y * y),
+            ],
+        ];
+        let s2 = [p.scale_0, p.scale_1, p.scale_2].map(|s| (2.0 * s).exp());
+        std::array::from_fn(|i| {
+            std::array::from_fn(|j| (0..3).map(|k| r[i][k] * s2[k] * r[j][k]).sum())
+        })
+    }
+
+    fn gaussian() -> PlyGaussian {
+        PlyGaussian {
+            x: 1.0,
+            y: 2.0,
+            z: 3.0,
+            scale_0: -1.0,
+            scale_1: 0.5,
+            scale_2: -0.25,
+            rot_0: 0.8,
+            rot_1: 0.3,
+            rot_2: -0.4,
+            rot_3: 0.33,
+            opacity: 1.0,
+            ..Default::default()
+        }
+    }
+
+    fn assert_mirrored(convention: AxisConvention, mirror: impl Fn([f32; 3]) -> [f32; 3]) {
+        let p = gaussian();
+        let flipped = convention.apply(p.clone());
+        assert_eq!([flipped.x, flipped.y, flipped.z], mirror([p.x, p.y, p.z]));
+
+        // Mirrored covariance: M Σ M, with M applied to rows and columns
+        let rows = covariance(&p).map(&mirror);
+        let columns: Mat3 = std::array::from_fn(|j| mirror(rows.map(|row| row[j])));
+        let expected: Mat3 = std::array::from_fn(|i| std::array::from_fn(|j| columns[j][i]));
+        let actual = covariance(&flipped);
+        for i in 0..3 {
+            for j in 0..3 {
+                assert!(
+                    (actual[i][j] - expected[i][j]).abs() < 1e-5,
+                    "{convention:?}: {actual:?} != {expected:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_flip_yz() {
+        assert_mirrored(AxisConvention::FlipYz, |[x, y, z]| [x, z, y]);
+    }
+
+    #[test]
+    fn test_flip_handedness() {
+        assert_mirrored(AxisConvention::FlipHandedness, |[x, y, z]| [x, y, -z]);
+    }
+
+    #[test]
+    fn test_conversion_with_axes() {
+        let options = ConvertOptions {
+            axes: AxisConvention::FlipYz,
+            ..Default::default()
+        };
+        let splats = ply_to_splat_with_options(vec![gaussian()], &options);
+        assert_eq!(splats[0].pos, [1.0, 3.0, 2.0]);
+        let unchanged = ply_to_splat_with_options(vec![gaussian()], &ConvertOptions::default());
+        assert_eq!(unchanged[0].pos, [1.0, 2.0, 3.0]);
+    }
+}
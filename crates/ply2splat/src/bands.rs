@@ -0,0 +1,241 @@
+//! Splitting a scene into opacity bands for progressive loading.
+//!
+//! Band 0 holds the most opaque splats and each following band holds more transparent ones,
+//! so a viewer can load opaque structure first and add transparent detail later. A splat with
+//! alpha `a` (its quantized alpha divided by 255) belongs to band `k` when
+//! `lower_k <= a < upper_k`; band 0 additionally includes `a == 1`. Bands may be unevenly
+//! populated or empty.
+
+use crate::dump::json_string;
+use crate::{SplatPoint, splats_to_bytes};
+use anyhow::{Context, Result, bail};
+use std::path::{Path, PathBuf};
+
+/// How the band boundaries are chosen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum BandBoundaries {
+    /// Boundaries split `[0, 1]` into equally wide alpha ranges.
+    #[default]
+    Uniform,
+    /// Boundaries are alpha quantiles, so bands hold about the same number of splats.
+    /// Splats with equal alpha always share a band, with ties going to the more opaque one.
+    Quantile,
+}
+
+/// The splats of one opacity band.
+#[derive(Debug, Clone)]
+pub struct OpacityBand {
+    /// Band index, 0 being the most opaque.
+    pub index: usize,
+    /// Inclusive lower alpha bound.
+    pub lower: f32,
+    /// Exclusive upper alpha bound (inclusive for band 0).
+    pub upper: f32,
+    /// Splats of the band, in their input order.
+    pub splats: Vec<SplatPoint>,
+}
+
+fn alpha(s: &SplatPoint) -> f32 {
+    s.color[3] as f32 / 255.0
+}
+
+/// Splits splats into `bands` opacity bands.
+///
+/// Splats keep their relative order within a band, so importance-sorted input yields
+/// importance-sorted bands.
+///
+/// # Panics
+/// Panics if `bands` is 0.
+pub fn split_opacity_bands(
+    splats: &[SplatPoint],
+    bands: usize,
+    boundaries: BandBoundaries,
+) -> Vec<OpacityBand> {
+    assert!(bands > 0, "bands must be positive");
+
+    // Descending boundaries: bound[0] = 1, bound[bands] = 0
+    let bounds: Vec<f32> = match boundaries {
+        BandBoundaries::Uniform => (0..=bands).map(|k| 1.0 - k as f32 / bands as f32).collect(),
+        BandBoundaries::Quantile => {
+            let mut alphas: Vec<f32> = splats.iter().map(alpha).collect();
+            alphas.sort_by(|a, b| b.total_cmp(a));
+            (0..=bands)
+                .map(|k| match k {
+                    0 => 1.0,
+                    k if k == bands => 0.0,
+                    k => alphas
+                        .get((k * alphas.len()).div_ceil(bands).saturating_sub(1))
+                        .copied()
+                        .unwrap_or(0.0),
+                })
+                .collect()
+        }
+    };
+
+    let mut result: Vec<OpacityBand> = (0..bands)
+        .map(|index| OpacityBand {
+            index,
+            lower: bounds[index + 1],
+            upper: bounds[index],
+            splats: Vec::new(),
+        })
+        .collect();
+    for s in splats {
+        let a = alpha(s);
+        let band = bounds[1..bands].iter().filter(|&&b| a < b).count();
+        result[band].splats.push(*s);
+    }
+    result
+}
+
+/// Path of band `index` for the output path `output`, e.g. `scene.splat` -> `scene.band0.splat`.
+pub fn band_path(output: &Path, index: usize) -> PathBuf {
+    sibling_path(output, &format!("band{index}.splat"))
+}
+
+/// Replaces the extension of `output` with `suffix`.
+fn sibling_path(output: &Path, suffix: &str) -> PathBuf {
+    let stem = output.file_stem().unwrap_or_default().to_string_lossy();
+    output.with_file_name(format!("{stem}.{suffix}"))
+}
+
+/// Builds the manifest describing `bands`, with file names relative to the manifest.
+pub fn bands_manifest_json(
+    output: &Path,
+    bands: &[OpacityBand],
+    boundaries: BandBoundaries,
+) -> String {
+    let entries: Vec<String> = bands
+        .iter()
+        .map(|b| {
+            let path = band_path(output, b.index);
+            let mut file = String::new();
+            json_string(&mut file, &path.file_name().unwrap_or_default().to_string_lossy());
+            format!(
+                "    {{\"index\": {}, \"file\": {}, \"count\": {}, \"min_alpha\": {}, \"max_alpha\": {}}}",
+                b.index,
+                file,
+                b.splats.len(),
+                b.lower,
+                b.upper
+            )
+        })
+        .collect();
+    let boundaries = match boundaries {
+        BandBoundaries::Uniform => "uniform",
+        BandBoundaries::Quantile => "quantile",
+    };
+    format!(
+        "{{\n  \"boundaries\": \"{}\",\n  \"bands\": [\n{}\n  ]\n}}\n",
+        boundaries,
+        entries.join(",\n")
+    )
+}
+
+/// Writes one file per opacity band next to `output` plus a `<stem>.bands.json` manifest.
+///
+/// # Returns
+/// The number of bands written.
+pub fn save_opacity_bands<P: AsRef<Path>>(
+    output: P,
+    splats: &[SplatPoint],
+    bands: usize,
+    boundaries: BandBoundaries,
+) -> Result<usize> {
+    if bands == 0 {
+        bail!("The number of opacity bands must be positive");
+    }
+    let output = output.as_ref();
+    let split = split_opacity_bands(splats, bands, boundaries);
+    for band in &split {
+        let path = band_path(output, band.index);
+        std::fs::write(&path, splats_to_bytes(&band.splats))
+            .with_context(|| format!("Failed to write band {:?}", path))?;
+    }
+    std::fs::write(
+        sibling_path(output, "bands.json"),
+        bands_manifest_json(output, &split, boundaries),
+    )
+    .context("Failed to write band manifest")?;
+    Ok(split.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn with_alphas(alphas: &[u8]) -> Vec<SplatPoint> {
+        alphas
+            .iter()
+            .enumerate()
+            .map(|(i, &a)| SplatPoint {
+                pos: [i as f32, 0.0, 0.0],
+                scale: [1.0; 3],
+                color: [255, 255, 255, a],
+                rot: [255, 128, 128, 128],
+            })
+            .collect()
+    }
+
+    fn members(band: &OpacityBand) -> Vec<u8> {
+        band.splats.iter().map(|s| s.color[3]).collect()
+    }
+
+    #[test]
+    fn test_uniform_bands() {
+        let splats = with_alphas(&[255, 0, 200, 191, 64, 63, 128, 10]);
+        let bands = split_opacity_bands(&splats, 4, BandBoundaries::Uniform);
+        assert_eq!(bands.len(), 4);
+        // [0.75, 1], [0.5, 0.75), [0.25, 0.5), [0, 0.25)
+        assert_eq!(members(&bands[0]), [255, 200]);
+        assert_eq!(members(&bands[1]), [191, 128]);
+        assert_eq!(members(&bands[2]), [64]);
+        assert_eq!(members(&bands[3]), [0, 63, 10]);
+        assert_eq!((bands[1].lower, bands[1].upper), (0.5, 0.75));
+    }
+
+    #[test]
+    fn test_quantile_bands() {
+        let splats = with_alphas(&[10, 20, 30, 40, 50, 60, 70, 80]);
+        let bands = split_opacity_bands(&splats, 4, BandBoundaries::Quantile);
+        assert_eq!(members(&bands[0]), [70, 80]);
+        assert_eq!(members(&bands[1]), [50, 60]);
+        assert_eq!(members(&bands[2]), [30, 40]);
+        assert_eq!(members(&bands[3]), [10, 20]);
+    }
+
+    #[test]
+    fn test_save_bands() {
+        let dir = tempfile::tempdir().unwrap();
+        let output = dir.path().join("scene.splat");
+        let splats = with_alphas(&[255, 0, 128]);
+        assert_eq!(
+            save_opacity_bands(&output, &splats, 2, BandBoundaries::Uniform).unwrap(),
+            2
+        );
+
+        let band0 = std::fs::read(dir.path().join("scene.band0.splat")).unwrap();
+        assert_eq!(band0, splats_to_bytes(&[splats[0], splats[2]]));
+        let band1 = std::fs::read(dir.path().join("scene.band1.splat")).unwrap();
+        assert_eq!(band1, splats_to_bytes(&[splats[1]]));
+
+        let manifest = std::fs::read_to_string(dir.path().join("scene.bands.json")).unwrap();
+        assert!(manifest.contains("\"boundaries\": \"uniform\""));
+        assert!(manifest.contains("\"file\": \"scene.band1.splat\", \"count\": 1"));
+    }
+
+    #[test]
+    fn test_manifest_escapes_file_names() {
+        let splats = with_alphas(&[255, 0]);
+        let bands = split_opacity_bands(&splats, 2, BandBoundaries::Uniform);
+        let manifest = bands_manifest_json(
+            Path::new("my\"scene.splat"),
+            &bands,
+            BandBoundaries::Uniform,
+        );
+        let parsed: serde_json::Value = serde_json::from_str(&manifest).unwrap();
+        assert_eq!(parsed["bands"][0]["file"], "my\"scene.band0.splat");
+        assert_eq!(parsed["bands"][1]["count"], 1);
+    }
+}
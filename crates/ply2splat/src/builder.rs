@@ -0,0 +1,156 @@
+//! Constructing and editing splats directly, without a PLY file.
+//!
+//! `SplatBuilder` collects `SplatPoint`s from explicit values or from existing data, lets
+//! callers recolor or move individual splats, and serializes them like converted output.
+
+use crate::{QuantizeMode, SplatPoint, splats_to_bytes};
+
+impl SplatPoint {
+    /// Creates a splat from linear scales, an RGBA color and a `(w, x, y, z)` quaternion.
+    ///
+    /// The quaternion is normalized and quantized like in conversion, so it need not be of
+    /// unit length.
+    pub fn new(pos: [f32; 3], scale: [f32; 3], color: [u8; 4], rotation: [f32; 4]) -> Self {
+        SplatPoint {
+            pos,
+            scale,
+            color,
+            rot: QuantizeMode::Truncate.rotation(rotation),
+        }
+    }
+}
+
+/// A growable, editable list of splats.
+#[derive(Debug, Clone, Default)]
+pub struct SplatBuilder {
+    splats: Vec<SplatPoint>,
+}
+
+impl SplatBuilder {
+    /// Creates an empty builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a splat built with `SplatPoint::new`.
+    pub fn push(
+        &mut self,
+        pos: [f32; 3],
+        scale: [f32; 3],
+        color: [u8; 4],
+        rotation: [f32; 4],
+    ) -> &mut Self {
+        self.push_splat(SplatPoint::new(pos, scale, color, rotation))
+    }
+
+    /// Appends an existing splat.
+    pub fn push_splat(&mut self, splat: SplatPoint) -> &mut Self {
+        self.splats.push(splat);
+        self
+    }
+
+    /// Moves the splat at `index`.
+    ///
+    /// # Panics
+    /// If `index` is out of bounds.
+    pub fn set_position(&mut self, index: usize, pos: [f32; 3]) -> &mut Self {
+        self.splats[index].pos = pos;
+        self
+    }
+
+    /// Recolors the splat at `index`.
+    ///
+    /// # Panics
+    /// If `index` is out of bounds.
+    pub fn set_color(&mut self, index: usize, color: [u8; 4]) -> &mut Self {
+        self.splats[index].color = color;
+        self
+    }
+
+    /// Number of splats.
+    pub fn len(&self) -> usize {
+        self.splats.len()
+    }
+
+    /// Whether there are no splats.
+    pub fn is_empty(&self) -> bool {
+        self.splats.is_empty()
+    }
+
+    /// The splats, in insertion order.
+    pub fn splats(&self) -> &[SplatPoint] {
+        &self.splats
+    }
+
+    /// The splats for arbitrary edits.
+    pub fn splats_mut(&mut self) -> &mut [SplatPoint] {
+        &mut self.splats
+    }
+
+    /// Serializes the splats like `splats_to_bytes`.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        splats_to_bytes(&self.splats)
+    }
+
+    /// Returns the splats.
+    pub fn build(self) -> Vec<SplatPoint> {
+        self.splats
+    }
+}
+
+impl From<Vec<SplatPoint>> for SplatBuilder {
+    fn from(splats: Vec<SplatPoint>) -> Self {
+        Self { splats }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{convert, splats_from_bytes};
+
+    #[test]
+    fn test_edit_round_trip() {
+        let ply = b"ply\nformat ascii 1.0\nelement vertex 3\nproperty float x\n\
+            property float y\nproperty float z\nproperty float f_dc_0\nproperty float f_dc_1\n\
+            property float f_dc_2\nproperty float opacity\nproperty float scale_0\n\
+            property float scale_1\nproperty float scale_2\nproperty float rot_0\n\
+            property float rot_1\nproperty float rot_2\nproperty float rot_3\nend_header\n\
+            0 0 0 0 0 0 1 0 0 0 1 0 0 0\n1 0 0 1 1 1 2 0 0 0 1 0 0 0\n2 0 0 -1 0 0 3 0 0 0 0 1 0 0\n";
+        let (original, _) = convert(ply, true).unwrap();
+
+        let mut builder = SplatBuilder::from(splats_from_bytes(&original).unwrap());
+        builder.set_color(1, [255, 0, 0, 255]);
+        let edited = builder.to_bytes();
+
+        assert_eq!(edited.len(), original.len());
+        for (i, (a, b)) in original.chunks(32).zip(edited.chunks(32)).enumerate() {
+            if i == 1 {
+                assert_eq!(a[..24], b[..24]);
+                assert_eq!(b[24..28], [255, 0, 0, 255]);
+                assert_eq!(a[28..], b[28..]);
+            } else {
+                assert_eq!(a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_push() {
+        let mut builder = SplatBuilder::new();
+        builder
+            .push(
+                [1.0, 2.0, 3.0],
+                [0.5; 3],
+                [10, 20, 30, 40],
+                [2.0, 0.0, 0.0, 0.0],
+            )
+            .push([0.0; 3], [1.0; 3], [0; 4], [0.0; 4]);
+        assert_eq!(builder.len(), 2);
+        let splats = builder.build();
+        assert_eq!(splats[0].pos, [1.0, 2.0, 3.0]);
+        assert_eq!(splats[0].rot, [255, 128, 128, 128]);
+        // A zero quaternion becomes the identity, as in conversion
+        assert_eq!(splats[1].rot, splats[0].rot);
+    }
+}
@@ -1,24 +1,386 @@
-use crate::{load_ply, ply_to_splat, save_splat};
-use anyhow::Result;
+use crate::{
+    ActivationMode, AxisConvention, BandBoundaries, ColorLut, ConvertOptions, ExtendedSplats,
+    LengthUnit, LoadOptions, MaskMode, OutputFormat, PlyGaussian, PlySchema, QuantizeMode,
+    RendererProfile, RotationOrder, SanitizeMode, ShMode, SortKey, SplatContainer, SplatFilter,
+    StreamedCounts, Transform, WarningHandler, compare_scenes, compute_stats,
+    convert_ply_to_splat_streaming_with_options, downsample_voxel, estimate_vram, in_thread_pool,
+    inspect_ply, inspect_ply_bytes, load_ply_from_bytes_with_options, load_ply_with_options,
+    looks_pre_activated, ply_to_splat_extended, ply_to_splat_with_normals,
+    ply_to_splat_with_options_and_progress, save_geotiles, save_normals, save_opacity_bands,
+    save_units_sidecar, subsample_to, to_soa, write_extended_splats, write_splat,
+    write_splat_headered, write_splats_as,
+};
+#[cfg(feature = "compress")]
+use crate::{Compression, write_splat_compressed};
+use anyhow::{Context, Result, bail};
 use clap::Parser;
 use indicatif::{ProgressBar, ProgressStyle};
-use std::path::PathBuf;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
 use std::time::Instant;
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 pub struct CliArgs {
-    /// Input PLY file
-    #[arg(short, long)]
-    pub input: PathBuf,
+    /// Input PLY file(s); glob patterns such as `captures/*.ply` are expanded and `-` reads
+    /// from stdin
+    #[arg(short, long, num_args = 1.., required = true)]
+    pub input: Vec<PathBuf>,
 
-    /// Output SPLAT file
-    #[arg(short, long)]
-    pub output: PathBuf,
+    /// Output SPLAT file (a directory when `--geotile` is used); several inputs are merged
+    /// into it and sorted together, and `-` writes to stdout
+    #[arg(short, long, required_unless_present_any = ["output_dir", "inspect"])]
+    pub output: Option<PathBuf>,
+
+    /// Convert every input into `<stem>.splat` inside this directory
+    #[arg(long, conflicts_with = "output")]
+    pub output_dir: Option<PathBuf>,
+
+    /// Print the header summary (elements, vertex count, properties) of each input and exit
+    /// without converting
+    #[arg(long)]
+    pub inspect: bool,
+
+    /// Convert batch inputs in parallel across files (needs the `parallel` feature)
+    #[arg(long)]
+    pub parallel_files: bool,
+
+    /// Stop a batch at the first file that fails instead of continuing with the rest
+    #[arg(long)]
+    pub fail_fast: bool,
 
     /// Disable sorting of splats
     #[arg(long)]
     pub no_sort: bool,
+
+    /// Key to sort splats by
+    #[arg(long, value_enum, default_value_t = SortBy::Importance, conflicts_with = "no_sort")]
+    pub sort_by: SortBy,
+
+    /// Camera position (x,y,z) for --sort-by distance
+    #[arg(
+        long,
+        value_parser = parse_vec3,
+        required_if_eq("sort_by", "distance"),
+        allow_hyphen_values = true
+    )]
+    pub camera: Option<[f32; 3]>,
+
+    /// Second PLY file to place next to the input for A/B comparison; needs --compare-offset
+    #[arg(long, requires = "compare_offset")]
+    pub compare: Option<PathBuf>,
+
+    /// Offset (x,y,z) applied to the comparison scene, so it does not overlap the input
+    #[arg(
+        long,
+        value_parser = parse_vec3,
+        requires = "compare",
+        allow_hyphen_values = true
+    )]
+    pub compare_offset: Option<[f32; 3]>,
+
+    /// Tint (r,g,b in 0..1) blended into the comparison scene's colors
+    #[arg(long, value_parser = parse_vec3, requires = "compare")]
+    pub compare_tint: Option<[f32; 3]>,
+
+    /// Evaluate higher-order spherical harmonics for this viewing direction (x,y,z)
+    /// instead of using only the DC color
+    #[arg(long, value_parser = parse_vec3)]
+    pub sh_view_dir: Option<[f32; 3]>,
+
+    /// Raw color lookup table: 256 bytes for all channels, or 768 bytes for red, green and
+    /// blue in turn, mapping each quantized color byte to the stored one
+    #[arg(long, value_name = "FILE")]
+    pub color_lut: Option<PathBuf>,
+
+    /// Print an estimate of the GPU memory needed by common renderers
+    #[arg(long, conflicts_with = "output_dir")]
+    pub estimate_vram: bool,
+
+    /// Save the input's PLY header next to the output (`<output>.header.ply`) so a
+    /// regenerated PLY can reproduce the original property order and types; the schema
+    /// describes a `vertex` element, so this cannot be combined with --element
+    #[arg(long, conflicts_with = "element")]
+    pub keep_schema: bool,
+
+    /// Split the output into world-aligned XY grid tiles of this size, written as
+    /// `tile_X_Y.splat` files plus a `tiles.json` index into the output directory
+    #[arg(long, value_name = "SIZE")]
+    pub geotile: Option<f32>,
+
+    /// Drop splats whose opacity (after sigmoid, 0..1) is below this value
+    #[arg(long, default_value_t = 0.0)]
+    pub min_opacity: f32,
+
+    /// Drop splats whose largest scale (after exp) exceeds this value
+    #[arg(long, default_value_t = f32::INFINITY)]
+    pub max_scale: f32,
+
+    /// Write the extended SPLAT format, keeping each splat's `confidence` property
+    #[arg(long, conflicts_with = "geotile")]
+    pub confidence: bool,
+
+    /// Also write each splat's normal (`nx`, `ny`, `nz`) to `<output>.normals`, as
+    /// little-endian f32 triples in splat order
+    #[arg(long, conflicts_with_all = ["geotile", "confidence", "opacity_bands"])]
+    pub normals: bool,
+
+    /// Split the output into N opacity bands, written as `<stem>.band0.splat` (most opaque)
+    /// through `<stem>.band<N-1>.splat` plus a `<stem>.bands.json` manifest
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u16).range(1..), conflicts_with_all = ["geotile", "confidence"])]
+    pub opacity_bands: Option<u16>,
+
+    /// How opacity band boundaries are chosen: equal alpha ranges or equal splat counts
+    #[arg(long, value_enum, default_value_t = BandBoundaries::Uniform)]
+    pub band_boundaries: BandBoundaries,
+
+    /// Hide splats whose value of this `vertex` property is non-zero
+    #[arg(long, value_name = "PROPERTY")]
+    pub mask_property: Option<String>,
+
+    /// What happens to splats hidden by `--mask-property`
+    #[arg(long, value_enum, default_value_t = MaskMode::Transparent, requires = "mask_property")]
+    pub mask_mode: MaskMode,
+
+    /// Translate every splat by (x,y,z), applied after rotation and scaling
+    #[arg(long, value_parser = parse_vec3, default_value = "0,0,0", allow_hyphen_values = true)]
+    pub translate: [f32; 3],
+
+    /// Rotate every splat by the quaternion (w,x,y,z)
+    #[arg(long, value_parser = parse_quat, default_value = "1,0,0,0", allow_hyphen_values = true)]
+    pub rotate_quat: [f32; 4],
+
+    /// Uniformly scale every splat by this factor
+    #[arg(long, default_value_t = 1.0)]
+    pub scale: f32,
+
+    /// Convert world units as FROM:TO (each m, cm or mm), scaling positions and splat sizes
+    /// alike and recording the target unit in `<output>.meta.json`
+    #[arg(long, value_name = "FROM:TO", value_parser = parse_units)]
+    pub units: Option<(LengthUnit, LengthUnit)>,
+
+    /// Swap the Y and Z axes (between Y-up and Z-up viewers), remapping rotations too
+    #[arg(long, conflicts_with = "flip_handedness")]
+    pub flip_yz: bool,
+
+    /// Negate the Z axis to switch handedness, remapping rotations too
+    #[arg(long)]
+    pub flip_handedness: bool,
+
+    /// Drop Gaussians with a NaN or infinite position, scale, opacity or rotation
+    #[arg(long)]
+    pub drop_invalid: bool,
+
+    /// Replace NaN and infinite values with 0 and make those Gaussians fully transparent
+    #[arg(long, conflicts_with = "drop_invalid")]
+    pub replace_invalid: bool,
+
+    /// Compress the output, appending .gz or .zst to its file name
+    #[cfg(feature = "compress")]
+    #[arg(long, value_enum, conflicts_with_all = ["geotile", "confidence", "opacity_bands", "soa", "format", "container"])]
+    pub compress: Option<Compression>,
+
+    /// Order of the quaternion components rot_0..rot_3 in the input
+    #[arg(long, value_enum, default_value_t = RotationOrder::Wxyz)]
+    pub rotation_order: RotationOrder,
+
+    /// How colors and rotations are rounded to bytes; round matches converters that
+    /// round to nearest
+    #[arg(long, value_enum, default_value_t = QuantizeMode::Truncate)]
+    pub quantize: QuantizeMode,
+
+    /// Output format; json and csv write human-readable per-splat values for debugging,
+    /// compact16 writes 20-byte records with half-float positions and scales
+    #[arg(long, value_enum, default_value_t = OutputFormat::Splat, conflicts_with_all = ["geotile", "confidence", "opacity_bands", "soa"])]
+    pub format: OutputFormat,
+
+    /// Wrap the records in a header with magic, version and splat count (headered), or
+    /// write them as they are (raw)
+    #[arg(long, value_enum, default_value_t = SplatContainer::Raw, conflicts_with_all = ["geotile", "confidence", "opacity_bands", "soa", "format"])]
+    pub container: SplatContainer,
+
+    /// Write a structure-of-arrays file (header plus position, scale, color and rotation
+    /// planes) instead of interleaved 32-byte records
+    #[arg(long, conflicts_with_all = ["geotile", "confidence", "opacity_bands"])]
+    pub soa: bool,
+
+    /// Merge splats whose centers share a voxel of this size, keeping the most opaque one
+    #[arg(long, value_name = "SIZE", value_parser = parse_positive)]
+    pub voxel_size: Option<f32>,
+
+    /// Randomly keep at most this many splats, e.g. for quick previews
+    #[arg(long, value_name = "N")]
+    pub max_splats: Option<usize>,
+
+    /// Seed for --max-splats; the same seed selects the same splats
+    #[arg(long, default_value_t = 0, requires = "max_splats")]
+    pub seed: u64,
+
+    /// Accept PLYs lacking some of the expected vertex properties, reading them as 0
+    #[arg(long)]
+    pub lenient: bool,
+
+    /// Fail on PLYs whose payload holds fewer vertices than the header declares, instead of
+    /// converting the ones present with a warning
+    #[arg(long)]
+    pub strict: bool,
+
+    /// Read scales and opacities as linear values instead of log-scales and logits
+    #[arg(long)]
+    pub pre_activated: bool,
+
+    /// Name of the PLY element holding the Gaussians, for exporters that do not use `vertex`
+    #[arg(long, value_name = "NAME")]
+    pub element: Option<String>,
+
+    /// Print bounds, scale and opacity statistics of the converted splats
+    #[arg(long, conflicts_with = "output_dir")]
+    pub stats: bool,
+
+    /// Number of worker threads (0 uses all available cores)
+    #[arg(long, default_value_t = 0)]
+    pub threads: usize,
+}
+
+/// Sort keys selectable on the command line.
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortBy {
+    /// Volume times opacity
+    Importance,
+    /// Coverage weighted by color contrast to neighboring splats
+    Perceptual,
+    /// Opacity alone, most opaque first
+    Opacity,
+    /// Distance to --camera, nearest first
+    Distance,
+    /// Morton (Z-order) code of the position, keeping nearby splats together
+    Morton,
+}
+
+/// Parses a comma-separated `x,y,z` triple.
+fn parse_vec3(s: &str) -> Result<[f32; 3], String> {
+    let values = s
+        .split(',')
+        .map(|v| v.trim().parse::<f32>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("invalid number in '{s}': {e}"))?;
+    values
+        .try_into()
+        .map_err(|_| format!("expected three comma-separated values, got '{s}'"))
+}
+
+/// Path standing for stdin as `--input` and for stdout as `--output`.
+const STDIO: &str = "-";
+
+fn is_stdio(path: &Path) -> bool {
+    path.as_os_str() == STDIO
+}
+
+/// Prints a human-readable line to stdout, or to stderr when stdout carries the output.
+macro_rules! report {
+    ($to_stderr:expr, $($arg:tt)*) => {
+        if $to_stderr {
+            eprintln!($($arg)*);
+        } else {
+            println!($($arg)*);
+        }
+    };
+}
+
+/// Warning handler printing the library's load warnings to stderr.
+fn stderr_warnings() -> WarningHandler {
+    WarningHandler::new(|warning| eprintln!("Warning: {warning}"))
+}
+
+/// Prints a progress message when the job runs on its own rather than in a batch.
+macro_rules! status {
+    ($job:expr, $($arg:tt)*) => {
+        if $job.verbose {
+            report!(is_stdio(&$job.output), $($arg)*);
+        }
+    };
+}
+
+impl CliArgs {
+    /// Whether `--compress` was given.
+    #[cfg(feature = "compress")]
+    fn compressed(&self) -> bool {
+        self.compress.is_some()
+    }
+
+    /// Whether `--compress` was given; it does not exist without the `compress` feature.
+    #[cfg(not(feature = "compress"))]
+    fn compressed(&self) -> bool {
+        false
+    }
+}
+
+/// Appends the extension of the `--compress` format, if any, to `path`.
+#[cfg(feature = "compress")]
+fn with_compression_extension(path: PathBuf, args: &CliArgs) -> PathBuf {
+    let Some(compression) = args.compress else {
+        return path;
+    };
+    let mut path = path.into_os_string();
+    path.push(".");
+    path.push(compression.extension());
+    path.into()
+}
+
+/// Returns `path` unchanged, as nothing is compressed without the `compress` feature.
+#[cfg(not(feature = "compress"))]
+fn with_compression_extension(path: PathBuf, _args: &CliArgs) -> PathBuf {
+    path
+}
+
+/// One input converted into one output.
+struct Job {
+    input: PathBuf,
+    /// Further inputs merged with `input` into one scene before conversion.
+    merge: Vec<PathBuf>,
+    output: PathBuf,
+    /// Print detailed progress; batch runs only print a summary line per file.
+    verbose: bool,
+}
+
+/// Vertex and splat counts of a finished job.
+struct Converted {
+    vertices: usize,
+    splats: usize,
+}
+
+/// Parses a comma-separated `w,x,y,z` quaternion.
+fn parse_quat(s: &str) -> Result<[f32; 4], String> {
+    let values = s
+        .split(',')
+        .map(|v| v.trim().parse::<f32>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("invalid number in '{s}': {e}"))?;
+    values
+        .try_into()
+        .map_err(|_| format!("expected four comma-separated values, got '{s}'"))
+}
+
+/// Parses a strictly positive number.
+fn parse_positive(s: &str) -> Result<f32, String> {
+    match s.parse::<f32>() {
+        Ok(v) if v > 0.0 => Ok(v),
+        Ok(_) => Err(format!("expected a positive number, got '{s}'")),
+        Err(e) => Err(format!("invalid number '{s}': {e}")),
+    }
+}
+
+/// Parses a `FROM:TO` unit pair such as `m:cm`.
+fn parse_units(s: &str) -> Result<(LengthUnit, LengthUnit), String> {
+    let (source, target) = s
+        .split_once(':')
+        .ok_or_else(|| format!("expected FROM:TO, got '{s}'"))?;
+    let parse = |unit: &str| unit.trim().parse::<LengthUnit>().map_err(|e| e.to_string());
+    Ok((parse(source)?, parse(target)?))
 }
 
 /// Runs the CLI logic with the given arguments.
@@ -30,49 +392,539 @@ where
     let args = CliArgs::parse_from(args);
     let start_total = Instant::now();
 
-    println!("Reading PLY file: {:?}", args.input);
+    let options = ConvertOptions {
+        sort: !args.no_sort,
+        sh_mode: args.sh_view_dir.map_or(ShMode::DcOnly, ShMode::EvaluateAt),
+        color_lut: match &args.color_lut {
+            Some(path) => Some(ColorLut::from_bytes(
+                &std::fs::read(path).context("Failed to read color LUT")?,
+            )?),
+            None => None,
+        },
+        filter: SplatFilter {
+            min_opacity: args.min_opacity,
+            max_scale: args.max_scale,
+        },
+        transform: Transform {
+            translation: args.translate,
+            rotation: args.rotate_quat,
+            scale: args.scale
+                * args
+                    .units
+                    .map_or(1.0, |(source, target)| source.factor_to(target)),
+        },
+        mask_mode: args.mask_mode,
+        sort_key: match args.sort_by {
+            SortBy::Importance => SortKey::Importance,
+            SortBy::Perceptual => SortKey::Perceptual,
+            SortBy::Morton => SortKey::Morton,
+            SortBy::Opacity => SortKey::Opacity,
+            SortBy::Distance => SortKey::DistanceFrom(args.camera.unwrap_or_default()),
+        },
+        axes: if args.flip_yz {
+            AxisConvention::FlipYz
+        } else if args.flip_handedness {
+            AxisConvention::FlipHandedness
+        } else {
+            AxisConvention::Unchanged
+        },
+        rotation_order: args.rotation_order,
+        quantize: args.quantize,
+        activation: if args.pre_activated {
+            ActivationMode::PreActivated
+        } else {
+            ActivationMode::Raw
+        },
+        sanitize: if args.drop_invalid {
+            SanitizeMode::Drop
+        } else if args.replace_invalid {
+            SanitizeMode::Replace
+        } else {
+            SanitizeMode::Keep
+        },
+        on_warning: stderr_warnings(),
+        // The whole run below already executes in the requested pool.
+        ..Default::default()
+    };
+    if args.scale.is_nan() || args.scale <= 0.0 {
+        bail!("--scale must be positive, got {}", args.scale);
+    }
+    let inputs = expand_inputs(&args.input)?;
+
+    if args.inspect {
+        for input in &inputs {
+            let info = if is_stdio(input) {
+                inspect_ply_bytes(&read_stdin()?)?
+            } else {
+                inspect_ply(input)?
+            };
+            println!("{:?}:\n{}", input, info);
+        }
+        return Ok(());
+    }
+
+    let to_stdout = args.output.as_deref().is_some_and(is_stdio);
+    if to_stdout
+        && (args.geotile.is_some()
+            || args.opacity_bands.is_some()
+            || args.normals
+            || args.keep_schema
+            || args.units.is_some())
+    {
+        bail!(
+            "--output - writes a single stream; --geotile, --opacity-bands, --normals, \
+             --keep-schema and --units need a file output"
+        );
+    }
+    let from_stdin = inputs.iter().any(|input| is_stdio(input));
+    if from_stdin && args.output_dir.is_some() {
+        bail!("--input - needs --output instead of --output-dir");
+    }
+    if from_stdin && args.keep_schema {
+        bail!("--keep-schema needs a file input, as it reads the header again");
+    }
+
+    match (&args.output, &args.output_dir) {
+        (Some(output), None) => {
+            let job = Job {
+                input: inputs[0].clone(),
+                merge: inputs[1..].to_vec(),
+                output: if to_stdout {
+                    output.clone()
+                } else {
+                    with_compression_extension(output.clone(), &args)
+                },
+                verbose: true,
+            };
+            let converted = in_thread_pool(args.threads, || convert_job(&args, &options, &job))?;
+            if args.estimate_vram {
+                print_vram_estimate(converted.splats, to_stdout);
+            }
+        }
+        (_, Some(dir)) => {
+            std::fs::create_dir_all(dir).context("Failed to create output directory")?;
+            let jobs = inputs
+                .iter()
+                .map(|input| Job {
+                    input: input.clone(),
+                    merge: Vec::new(),
+                    output: with_compression_extension(
+                        dir.join(input.file_stem().unwrap_or_default())
+                            .with_extension(args.format.extension()),
+                        &args,
+                    ),
+                    verbose: false,
+                })
+                .collect::<Vec<_>>();
+            check_output_collisions(&jobs)?;
+            in_thread_pool(args.threads, || run_batch(&args, &options, &jobs))?;
+        }
+        (None, None) => unreachable!("clap requires --output or --output-dir"),
+    }
+
+    report!(
+        to_stdout,
+        "Total time: {:.2}s",
+        start_total.elapsed().as_secs_f32()
+    );
+
+    Ok(())
+}
+
+/// Reads all of stdin, for `--input -`.
+fn read_stdin() -> Result<Vec<u8>> {
+    let mut data = Vec::new();
+    std::io::stdin()
+        .lock()
+        .read_to_end(&mut data)
+        .context("Failed to read PLY data from stdin")?;
+    Ok(data)
+}
+
+/// Loads an input PLY, `-` meaning stdin.
+fn load_input(path: &Path, options: &LoadOptions) -> Result<Vec<PlyGaussian>> {
+    if is_stdio(path) {
+        Ok(load_ply_from_bytes_with_options(&read_stdin()?, options)?)
+    } else {
+        Ok(load_ply_with_options(path, options)?)
+    }
+}
+
+/// Opens the output for writing, `-` meaning a locked stdout.
+fn create_output(path: &Path) -> Result<BufWriter<Box<dyn Write>>> {
+    let output: Box<dyn Write> = if is_stdio(path) {
+        Box::new(std::io::stdout().lock())
+    } else {
+        Box::new(File::create(path).context("Failed to create output file")?)
+    };
+    Ok(BufWriter::new(output))
+}
+
+/// Expands glob patterns among `inputs`; other paths are kept as given.
+fn expand_inputs(inputs: &[PathBuf]) -> Result<Vec<PathBuf>> {
+    let mut expanded = Vec::new();
+    for input in inputs {
+        let pattern = input.to_string_lossy();
+        if input.exists() || !pattern.contains(['*', '?', '[']) {
+            expanded.push(input.clone());
+            continue;
+        }
+        let matches = glob::glob(&pattern)
+            .with_context(|| format!("Invalid glob pattern {:?}", pattern))?
+            .collect::<Result<Vec<_>, _>>()?;
+        if matches.is_empty() {
+            bail!("No files match {:?}", pattern);
+        }
+        expanded.extend(matches);
+    }
+    Ok(expanded)
+}
+
+/// Fails if several jobs would write the same output, e.g. inputs sharing a file stem,
+/// listing the colliding inputs.
+fn check_output_collisions(jobs: &[Job]) -> Result<()> {
+    let mut by_output: BTreeMap<&Path, Vec<&Path>> = BTreeMap::new();
+    for job in jobs {
+        by_output.entry(&job.output).or_default().push(&job.input);
+    }
+    let collisions = by_output
+        .iter()
+        .filter(|(_, inputs)| inputs.len() > 1)
+        .map(|(output, inputs)| format!("{:?} <- {:?}", output, inputs))
+        .collect::<Vec<_>>();
+    if !collisions.is_empty() {
+        bail!(
+            "Several inputs would write the same output file:\n  {}",
+            collisions.join("\n  ")
+        );
+    }
+    Ok(())
+}
+
+/// Converts every job, printing one summary line per file; failures go to stderr.
+///
+/// A failing file is reported and skipped unless `--fail-fast` is set, in which case the
+/// first error is returned. Otherwise an error is returned at the end if any file failed.
+fn run_batch(args: &CliArgs, options: &ConvertOptions, jobs: &[Job]) -> Result<()> {
+    let run_one = |job: &Job| -> Result<()> {
+        let start = Instant::now();
+        match convert_job(args, options, job) {
+            Ok(converted) => {
+                println!(
+                    "{}: {} vertices -> {} splats in {:.2}s",
+                    job.input.display(),
+                    converted.vertices,
+                    converted.splats,
+                    start.elapsed().as_secs_f32()
+                );
+                Ok(())
+            }
+            Err(e) => {
+                eprintln!("{}: failed: {:#}", job.input.display(), e);
+                Err(e.context(format!("Failed to convert {:?}", job.input)))
+            }
+        }
+    };
+
+    if args.fail_fast {
+        #[cfg(feature = "parallel")]
+        if args.parallel_files {
+            return jobs.par_iter().try_for_each(run_one);
+        }
+        return jobs.iter().try_for_each(run_one);
+    }
+
+    #[cfg(feature = "parallel")]
+    let results: Vec<Result<()>> = if args.parallel_files {
+        jobs.par_iter().map(run_one).collect()
+    } else {
+        jobs.iter().map(run_one).collect()
+    };
+    #[cfg(not(feature = "parallel"))]
+    let results: Vec<Result<()>> = jobs.iter().map(run_one).collect();
+
+    let failed = results.iter().filter(|r| r.is_err()).count();
+    println!("Converted {} of {} files", jobs.len() - failed, jobs.len());
+    if failed > 0 {
+        bail!("{} of {} files failed to convert", failed, jobs.len());
+    }
+    Ok(())
+}
+
+/// Converts one input, choosing between the streaming and the buffered path.
+fn convert_job(args: &CliArgs, options: &ConvertOptions, job: &Job) -> Result<Converted> {
+    // Without sorting or whole-scene passes, convert in chunks to bound memory use.
+    let converted = if args.no_sort
+        && args.compare.is_none()
+        && args.geotile.is_none()
+        && args.opacity_bands.is_none()
+        && args.mask_property.is_none()
+        && !args.stats
+        && args.voxel_size.is_none()
+        && args.max_splats.is_none()
+        && !args.lenient
+        && !args.strict
+        && args.element.is_none()
+        && !args.compressed()
+        && job.merge.is_empty()
+        && !args.soa
+        && args.format == OutputFormat::Splat
+        && args.container == SplatContainer::Raw
+        && !args.confidence
+        && !args.normals
+        && !is_stdio(&job.input)
+    {
+        convert_streaming(options, job)?
+    } else {
+        convert_buffered(args, options, job)?
+    };
+
+    if args.keep_schema {
+        let mut schema_path = job.output.clone().into_os_string();
+        schema_path.push(".header.ply");
+        PlySchema::from_file(&job.input)?.save(&schema_path)?;
+        status!(job, "Saved PLY schema to {:?}", schema_path);
+    }
+    if let Some((_, target)) = args.units {
+        let mut meta_path = job.output.clone().into_os_string();
+        meta_path.push(".meta.json");
+        save_units_sidecar(&meta_path, target)?;
+        status!(
+            job,
+            "Recorded units '{}' in {:?}",
+            target.symbol(),
+            meta_path
+        );
+    }
+    Ok(converted)
+}
+
+fn print_vram_estimate(count: usize, to_stderr: bool) {
+    report!(to_stderr, "Estimated VRAM for {} splats:", count);
+    for profile in RendererProfile::ALL {
+        let bytes = estimate_vram(count, &profile);
+        report!(
+            to_stderr,
+            "  {:<28} {:>10.1} MiB",
+            profile.name,
+            bytes as f64 / (1024.0 * 1024.0)
+        );
+    }
+}
+
+/// Streams the input through the conversion straight into the output file.
+fn convert_streaming(options: &ConvertOptions, job: &Job) -> Result<Converted> {
+    status!(
+        job,
+        "Streaming {:?} to {:?} (sorting disabled)...",
+        job.input,
+        job.output
+    );
+    let start = Instant::now();
+    let input = File::open(&job.input).context("Failed to open PLY file")?;
+    let mut writer = create_output(&job.output)?;
+    let StreamedCounts { vertices, splats } =
+        convert_ply_to_splat_streaming_with_options(input, &mut writer, options)?;
+    writer.flush()?;
+    status!(
+        job,
+        "Converted {} splats in {:.2}s",
+        splats,
+        start.elapsed().as_secs_f32()
+    );
+    Ok(Converted { vertices, splats })
+}
+
+/// Loads the whole scene, converts it and writes the output.
+fn convert_buffered(args: &CliArgs, options: &ConvertOptions, job: &Job) -> Result<Converted> {
+    status!(job, "Reading PLY file: {:?}", job.input);
     let start_read = Instant::now();
-    let ply_data = load_ply(&args.input)?;
+    let load_options = LoadOptions {
+        sh_rest: options.sh_mode != ShMode::DcOnly,
+        mask_property: args.mask_property.clone(),
+        allow_missing: args.lenient,
+        element: args.element.clone(),
+        strict: args.strict,
+        on_warning: options.on_warning.clone(),
+    };
+    let mut ply_data = load_input(&job.input, &load_options)?;
+    // Appending in place matches `merge_ply` without copying the scene
+    for path in &job.merge {
+        status!(job, "Reading PLY file to merge: {:?}", path);
+        ply_data.extend(load_input(path, &load_options)?);
+    }
+    if let Some(compare) = &args.compare {
+        status!(job, "Reading comparison PLY file: {:?}", compare);
+        let compare_data = load_ply_with_options(compare, &load_options)?;
+        ply_data = compare_scenes(
+            ply_data,
+            compare_data,
+            args.compare_offset
+                .expect("clap requires --compare-offset with --compare"),
+            args.compare_tint,
+        );
+    }
     let duration_read = start_read.elapsed();
-    println!(
+    status!(
+        job,
         "Loaded {} vertices in {:.2}s",
         ply_data.len(),
         duration_read.as_secs_f32()
     );
+    let vertices = ply_data.len();
+    if options.activation == ActivationMode::Raw && looks_pre_activated(&ply_data) {
+        eprintln!(
+            "Warning: all scales are between 0 and 1, so they look linear rather than \
+             logarithmic; pass --pre-activated if the file stores activated values"
+        );
+    }
+
+    if let Some(voxel_size) = args.voxel_size {
+        ply_data = downsample_voxel(ply_data, voxel_size);
+        status!(
+            job,
+            "Downsampled to {} splats with a voxel size of {}",
+            ply_data.len(),
+            voxel_size
+        );
+    }
+
+    if let Some(max_splats) = args.max_splats
+        && ply_data.len() > max_splats
+    {
+        ply_data = subsample_to(ply_data, max_splats, args.seed);
+        status!(job, "Subsampled to {} splats", ply_data.len());
+    }
 
     if args.no_sort {
-        println!("Processing (sorting disabled)...");
+        status!(job, "Processing (sorting disabled)...");
     } else {
-        println!("Processing and sorting...");
+        status!(job, "Processing and sorting...");
     }
     let start_process = Instant::now();
 
-    let pb = ProgressBar::new_spinner();
+    let loaded = ply_data.len();
+    // Only the plain conversion reports progress; the extended ones would leave the bar at 0
+    let pb = if job.verbose && !args.confidence && !args.normals {
+        ProgressBar::new(loaded as u64)
+    } else {
+        ProgressBar::hidden()
+    };
     pb.set_style(
-        ProgressStyle::default_spinner()
-            .tick_chars("/|\\- ")
-            .template("{spinner} {msg}")
-            .unwrap(),
+        ProgressStyle::default_bar()
+            .template("{msg} [{bar:40}] {pos}/{len} ({eta})")
+            .unwrap()
+            .progress_chars("=> "),
     );
-    pb.set_message("Converting...");
+    pb.set_message("Converting");
 
-    let splats = ply_to_splat(ply_data, !args.no_sort);
+    let mut normals = None;
+    let splats = if args.confidence {
+        ply_to_splat_extended(ply_data, options)
+    } else if args.normals {
+        let (splats, n) = ply_to_splat_with_normals(ply_data, options);
+        normals = Some(n);
+        ExtendedSplats {
+            splats,
+            confidence: None,
+        }
+    } else {
+        let splats = ply_to_splat_with_options_and_progress(ply_data, options, |done, _| {
+            pb.set_position(done as u64);
+            if done == loaded && options.sort {
+                pb.set_message("Sorting");
+            }
+        });
+        ExtendedSplats {
+            splats,
+            confidence: None,
+        }
+    };
 
     pb.finish_with_message("Conversion complete");
     let duration_process = start_process.elapsed();
-    println!("Processed in {:.2}s", duration_process.as_secs_f32());
+    status!(job, "Processed in {:.2}s", duration_process.as_secs_f32());
+    let count = splats.splats.len();
+    if count < loaded {
+        status!(job, "Filtered out {} splats", loaded - count);
+    }
+
+    if args.stats {
+        report!(is_stdio(&job.output), "{}", compute_stats(&splats.splats));
+    }
 
-    println!("Writing SPLAT file: {:?}", args.output);
     let start_write = Instant::now();
-    save_splat(&args.output, &splats)?;
+    if let Some(tile_size) = args.geotile {
+        status!(job, "Writing SPLAT tiles to: {:?}", job.output);
+        let tiles = save_geotiles(&job.output, &splats.splats, tile_size)?;
+        status!(job, "Wrote {} tiles", tiles);
+    } else if let Some(bands) = args.opacity_bands {
+        status!(job, "Writing SPLAT opacity bands next to: {:?}", job.output);
+        let bands = save_opacity_bands(
+            &job.output,
+            &splats.splats,
+            bands as usize,
+            args.band_boundaries,
+        )?;
+        status!(job, "Wrote {} bands", bands);
+    } else {
+        let mut writer = create_output(&job.output)?;
+        write_records(args, job, &mut writer, &splats)?;
+        writer.flush().context("Failed to write output file")?;
+    }
+    if let Some(normals) = normals {
+        let mut normals_path = job.output.clone().into_os_string();
+        normals_path.push(".normals");
+        save_normals(&normals_path, &normals)?;
+        status!(job, "Saved normals to {:?}", normals_path);
+    }
     let duration_write = start_write.elapsed();
-    println!(
+    status!(
+        job,
         "Written to {:?} in {:.2}s",
-        args.output,
+        job.output,
         duration_write.as_secs_f32()
     );
 
-    println!("Total time: {:.2}s", start_total.elapsed().as_secs_f32());
+    Ok(Converted {
+        vertices,
+        splats: count,
+    })
+}
 
+/// Writes the splats of `job` to `writer` in the format selected by `args`.
+fn write_records<W: Write>(
+    args: &CliArgs,
+    job: &Job,
+    writer: &mut W,
+    splats: &ExtendedSplats,
+) -> Result<()> {
+    #[cfg(feature = "compress")]
+    if let Some(compression) = args.compress {
+        status!(
+            job,
+            "Writing {:?} compressed SPLAT file: {:?}",
+            compression,
+            job.output
+        );
+        write_splat_compressed(writer, &splats.splats, compression)?;
+        return Ok(());
+    }
+    if args.soa {
+        status!(job, "Writing SoA SPLAT file: {:?}", job.output);
+        writer.write_all(&to_soa(&splats.splats))?;
+    } else if splats.confidence.is_some() {
+        status!(job, "Writing extended SPLAT file: {:?}", job.output);
+        write_extended_splats(writer, splats)?;
+    } else if args.container == SplatContainer::Headered {
+        status!(job, "Writing headered SPLAT file: {:?}", job.output);
+        write_splat_headered(writer, &splats.splats)?;
+    } else if args.format != OutputFormat::Splat {
+        status!(job, "Writing {:?} file: {:?}", args.format, job.output);
+        write_splats_as(writer, &splats.splats, args.format)?;
+    } else {
+        status!(job, "Writing SPLAT file: {:?}", job.output);
+        write_splat(writer, &splats.splats)?;
+    }
     Ok(())
 }
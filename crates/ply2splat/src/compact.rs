@@ -0,0 +1,155 @@
+//! Alternate record layouts for converted splats.
+//!
+//! The standard SPLAT record is 32 bytes. `SplatFormat::Compact16` stores position and
+//! scale as half floats in a 20-byte record, for bandwidth-bound renderers:
+//!
+//! | Offset | Size | Content                                     |
+//! |--------|------|---------------------------------------------|
+//! | 0      | 6    | Position x, y, z as little-endian `f16`     |
+//! | 6      | 6    | Scale x, y, z as little-endian `f16`        |
+//! | 12     | 4    | Color RGBA, `u8` each (as in the standard)  |
+//! | 16     | 4    | Rotation quaternion, `u8` each (as above)   |
+//!
+//! The files carry no header, so readers must know the 20-byte stride up front.
+//! `f16` keeps about three significant decimal digits: a position of magnitude `m` is off
+//! by up to `m / 2048`, and magnitudes above 65504 become infinite. Scenes far from the
+//! origin should be recentered (see `Transform`) before writing this layout.
+
+use crate::{SplatBytesError, SplatPoint};
+use half::f16;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Record layout of raw SPLAT data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SplatFormat {
+    /// 32-byte records with `f32` position and scale.
+    #[default]
+    Standard,
+    /// 20-byte records with `f16` position and scale.
+    Compact16,
+}
+
+impl SplatFormat {
+    /// Size of one record in bytes.
+    pub fn stride(self) -> usize {
+        match self {
+            Self::Standard => size_of::<SplatPoint>(),
+            Self::Compact16 => 20,
+        }
+    }
+}
+
+fn encode_compact(splat: &SplatPoint, out: &mut [u8]) {
+    for (i, v) in splat.pos.iter().chain(&splat.scale).enumerate() {
+        out[i * 2..i * 2 + 2].copy_from_slice(&f16::from_f32(*v).to_le_bytes());
+    }
+    out[12..16].copy_from_slice(&splat.color);
+    out[16..20].copy_from_slice(&splat.rot);
+}
+
+fn decode_compact(record: &[u8]) -> SplatPoint {
+    let half = |i: usize| f16::from_le_bytes([record[i * 2], record[i * 2 + 1]]).to_f32();
+    SplatPoint {
+        pos: [half(0), half(1), half(2)],
+        scale: [half(3), half(4), half(5)],
+        color: record[12..16].try_into().unwrap(),
+        rot: record[16..20].try_into().unwrap(),
+    }
+}
+
+/// Converts splats to raw bytes in the given record layout.
+///
+/// `SplatFormat::Standard` gives the same bytes as `splats_to_bytes`. Compact records are
+/// encoded in parallel when the `parallel` feature is enabled.
+pub fn splats_to_bytes_format(splats: &[SplatPoint], format: SplatFormat) -> Vec<u8> {
+    let stride = format.stride();
+    match format {
+        SplatFormat::Standard => bytemuck::cast_slice(splats).to_vec(),
+        SplatFormat::Compact16 => {
+            let mut out = vec![0u8; splats.len() * stride];
+            #[cfg(feature = "parallel")]
+            out.par_chunks_exact_mut(stride)
+                .zip(splats)
+                .for_each(|(record, splat)| encode_compact(splat, record));
+            #[cfg(not(feature = "parallel"))]
+            out.chunks_exact_mut(stride)
+                .zip(splats)
+                .for_each(|(record, splat)| encode_compact(splat, record));
+            out
+        }
+    }
+}
+
+/// Converts raw bytes in the given record layout back into `SplatPoint`s, the inverse of
+/// `splats_to_bytes_format` up to `f16` precision.
+pub fn splats_from_bytes_format(
+    data: &[u8],
+    format: SplatFormat,
+) -> Result<Vec<SplatPoint>, SplatBytesError> {
+    match format {
+        SplatFormat::Standard => crate::splats_from_bytes(data),
+        SplatFormat::Compact16 => {
+            if !data.len().is_multiple_of(format.stride()) {
+                return Err(SplatBytesError::InvalidCompactLength(data.len()));
+            }
+            Ok(data
+                .chunks_exact(format.stride())
+                .map(decode_compact)
+                .collect())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{PlyGaussian, ply_to_splat, splats_to_bytes};
+
+    #[test]
+    fn test_compact16_round_trip() {
+        let points: Vec<PlyGaussian> = (0..100)
+            .map(|i| {
+                let t = i as f32;
+                PlyGaussian {
+                    x: t * 0.37 - 18.0,
+                    y: (t * 0.11).sin() * 5.0,
+                    z: 1000.0 - t * 3.3,
+                    scale_0: -4.0 + t * 0.01,
+                    opacity: t * 0.1 - 5.0,
+                    rot_0: 1.0,
+                    rot_1: t * 0.01,
+                    ..Default::default()
+                }
+            })
+            .collect();
+        let splats = ply_to_splat(points, true);
+
+        let bytes = splats_to_bytes_format(&splats, SplatFormat::Compact16);
+        assert_eq!(bytes.len(), splats.len() * 20);
+        let back = splats_from_bytes_format(&bytes, SplatFormat::Compact16).unwrap();
+        assert_eq!(back.len(), splats.len());
+        for (a, b) in splats.iter().zip(&back) {
+            for (x, y) in a
+                .pos
+                .iter()
+                .chain(&a.scale)
+                .zip(b.pos.iter().chain(&b.scale))
+            {
+                // Half of the f16 epsilon, relative to the magnitude
+                assert!((x - y).abs() <= x.abs() / 2048.0, "{x} vs {y}");
+            }
+            assert_eq!(a.color, b.color);
+            assert_eq!(a.rot, b.rot);
+        }
+
+        assert_eq!(
+            splats_to_bytes_format(&splats, SplatFormat::Standard),
+            splats_to_bytes(&splats)
+        );
+        assert_eq!(
+            splats_from_bytes_format(&bytes[..30], SplatFormat::Compact16).unwrap_err(),
+            SplatBytesError::InvalidCompactLength(30)
+        );
+    }
+}
@@ -0,0 +1,88 @@
+use crate::{PlyGaussian, SH_C0};
+
+/// Combines two scenes into a single scene for side-by-side A/B comparison.
+///
+/// Every Gaussian of `scene_b` is translated by `offset` and, if `tint` is given, its base
+/// color is blended halfway towards the tint (RGB in `[0, 1]`) so both scenes can be told apart
+/// in a viewer. The result contains all of `scene_a` followed by the shifted `scene_b`.
+///
+/// Because the scenes are combined *before* conversion, passing the result to `ply_to_splat`
+/// converts and sorts both with exactly the same options.
+///
+/// # Arguments
+/// * `scene_a` - The reference scene, left untouched.
+/// * `scene_b` - The scene to compare against, shifted by `offset`.
+/// * `offset` - Translation (x, y, z) applied to `scene_b`.
+/// * `tint` - Optional RGB tint applied to `scene_b`.
+///
+/// # Returns
+/// The combined list of Gaussians.
+pub fn compare_scenes(
+    mut scene_a: Vec<PlyGaussian>,
+    scene_b: Vec<PlyGaussian>,
+    offset: [f32; 3],
+    tint: Option<[f32; 3]>,
+) -> Vec<PlyGaussian> {
+    scene_a.reserve(scene_b.len());
+    scene_a.extend(scene_b.into_iter().map(|mut p| {
+        p.x += offset[0];
+        p.y += offset[1];
+        p.z += offset[2];
+        if let Some(tint) = tint {
+            p.f_dc_0 = tint_dc(p.f_dc_0, tint[0]);
+            p.f_dc_1 = tint_dc(p.f_dc_1, tint[1]);
+            p.f_dc_2 = tint_dc(p.f_dc_2, tint[2]);
+        }
+        p
+    }));
+    scene_a
+}
+
+/// Blends the color encoded by an SH DC coefficient halfway towards `target`.
+fn tint_dc(f_dc: f32, target: f32) -> f32 {
+    let color = 0.5 + SH_C0 * f_dc;
+    let blended = 0.5 * color + 0.5 * target;
+    (blended - 0.5) / SH_C0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ply_to_splat;
+
+    #[test]
+    fn test_compare_scenes_offset() {
+        let a = vec![PlyGaussian {
+            x: 1.0,
+            ..Default::default()
+        }];
+        let b = vec![PlyGaussian {
+            x: 1.0,
+            y: 2.0,
+            ..Default::default()
+        }];
+
+        let combined = compare_scenes(a, b, [-10.0, 0.0, 0.0], None);
+        assert_eq!(combined.len(), 2);
+
+        let splats = ply_to_splat(combined, true);
+        let mut xs: Vec<f32> = splats.iter().map(|s| s.pos[0]).collect();
+        xs.sort_by(f32::total_cmp);
+        assert_eq!(xs, vec![-9.0, 1.0]);
+
+        let shifted = splats.iter().find(|s| s.pos[0] == -9.0).unwrap();
+        assert_eq!(shifted.pos[1], 2.0);
+    }
+
+    #[test]
+    fn test_compare_scenes_tint() {
+        let b = vec![PlyGaussian::default()];
+        let combined = compare_scenes(Vec::new(), b, [0.0; 3], Some([1.0, 0.0, 0.0]));
+        let splat = ply_to_splat(combined, false)[0];
+
+        // Gray (0.5) blended halfway towards red
+        assert_eq!(splat.color[0], 191);
+        assert_eq!(splat.color[1], 63);
+        assert_eq!(splat.color[2], 63);
+    }
+}
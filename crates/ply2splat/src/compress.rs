@@ -0,0 +1,123 @@
+//! Compressed SPLAT output (`compress` feature).
+//!
+//! SPLAT records compress well, since positions are spatially coherent and colors and
+//! rotations are quantized to bytes. Compressing on write saves a separate pipeline step
+//! when the files are served over the network.
+
+use crate::SplatPoint;
+use anyhow::{Context, Result};
+use flate2::write::GzEncoder;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// Compression format of the output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum Compression {
+    /// Gzip at the default level, readable by browsers through `Content-Encoding: gzip`.
+    Gzip,
+    /// Zstandard at the default level; usually smaller and faster than gzip.
+    Zstd,
+}
+
+impl Compression {
+    /// File extension appended to the output path, without the leading dot.
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Gzip => "gz",
+            Self::Zstd => "zst",
+        }
+    }
+}
+
+/// Writes `splats` as SPLAT records to `writer`, compressed with `compression`.
+///
+/// The compressed stream is finished before returning, so the output is complete once this
+/// returns `Ok`; the writer itself is not flushed beyond that.
+///
+/// # Returns
+/// The underlying writer.
+pub fn write_splat_compressed<W: Write>(
+    writer: W,
+    splats: &[SplatPoint],
+    compression: Compression,
+) -> Result<W> {
+    let bytes: &[u8] = bytemuck::cast_slice(splats);
+    let writer = match compression {
+        Compression::Gzip => {
+            let mut encoder = GzEncoder::new(writer, flate2::Compression::default());
+            encoder.write_all(bytes)?;
+            encoder.finish()?
+        }
+        Compression::Zstd => {
+            let mut encoder = zstd::Encoder::new(writer, zstd::DEFAULT_COMPRESSION_LEVEL)?;
+            encoder.write_all(bytes)?;
+            encoder.finish()?
+        }
+    };
+    Ok(writer)
+}
+
+/// Saves `splats` to a file compressed with `compression`.
+///
+/// The path is used as given; see `Compression::extension` for the customary extension.
+pub fn save_splat_compressed<P: AsRef<Path>>(
+    path: P,
+    splats: &[SplatPoint],
+    compression: Compression,
+) -> Result<()> {
+    let f = File::create(path).context("Failed to create output file")?;
+    let mut writer = write_splat_compressed(BufWriter::new(f), splats, compression)
+        .context("Failed to write compressed SPLAT data")?;
+    writer.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::splats_to_bytes;
+    use std::io::Read;
+
+    fn splats() -> Vec<SplatPoint> {
+        (0..100)
+            .map(|i| SplatPoint {
+                pos: [i as f32 * 0.1, 1.0, -2.0],
+                scale: [0.01; 3],
+                color: [i as u8, 128, 64, 255],
+                rot: [255, 128, 128, 128],
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let splats = splats();
+        let raw = splats_to_bytes(&splats);
+
+        let gzip = write_splat_compressed(Vec::new(), &splats, Compression::Gzip).unwrap();
+        let mut decoded = Vec::new();
+        flate2::read::GzDecoder::new(&gzip[..])
+            .read_to_end(&mut decoded)
+            .unwrap();
+        assert_eq!(decoded, raw);
+        assert!(gzip.len() < raw.len());
+
+        let zstd = write_splat_compressed(Vec::new(), &splats, Compression::Zstd).unwrap();
+        assert_eq!(zstd::decode_all(&zstd[..]).unwrap(), raw);
+        assert!(zstd.len() < raw.len());
+    }
+
+    #[test]
+    fn test_save() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("scene.splat.zst");
+        save_splat_compressed(&path, &splats(), Compression::Zstd).unwrap();
+        let data = std::fs::read(&path).unwrap();
+        assert_eq!(
+            zstd::decode_all(&data[..]).unwrap(),
+            splats_to_bytes(&splats())
+        );
+    }
+}
@@ -0,0 +1,121 @@
+//! SPLAT records behind a small header, for viewers that validate their input.
+//!
+//! ## Layout
+//!
+//! All integers are little-endian. A 12-byte header precedes the usual 32-byte records:
+//!
+//! | Offset | Size     | Content                                  |
+//! |--------|----------|------------------------------------------|
+//! | 0      | 4        | Magic `SPLT`                             |
+//! | 4      | 4        | Format version (`u32`, currently 1)      |
+//! | 8      | 4        | Splat count `n` (`u32`)                  |
+//! | 12     | `n * 32` | `SplatPoint` records, as in `save_splat` |
+//!
+//! The records start at a multiple of 4 bytes, so a buffer holding the whole file can be
+//! borrowed with `splats_from_bytes_ref(&data[SPLAT_HEADER_SIZE..])`.
+
+use crate::{SplatPoint, splats_from_bytes, write_splat};
+use anyhow::{Context, Result, bail};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// Magic bytes at the start of headered SPLAT data.
+pub const SPLAT_MAGIC: [u8; 4] = *b"SPLT";
+/// Current version of the headered SPLAT layout.
+pub const SPLAT_CONTAINER_VERSION: u32 = 1;
+/// Size of the headered SPLAT header in bytes.
+pub const SPLAT_HEADER_SIZE: usize = 12;
+
+/// How SPLAT records are wrapped in the output file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum SplatContainer {
+    /// Records only, as read by most viewers.
+    #[default]
+    Raw,
+    /// The header described in the module documentation, then the records.
+    Headered,
+}
+
+/// Writes splats with the header described in the module documentation.
+pub fn write_splat_headered<W: Write>(writer: &mut W, splats: &[SplatPoint]) -> Result<()> {
+    let count = u32::try_from(splats.len()).context("Too many splats for a SPLAT header")?;
+    writer.write_all(&SPLAT_MAGIC)?;
+    writer.write_all(&SPLAT_CONTAINER_VERSION.to_le_bytes())?;
+    writer.write_all(&count.to_le_bytes())?;
+    write_splat(writer, splats)
+}
+
+/// Saves splats with the header described in the module documentation.
+pub fn save_splat_headered<P: AsRef<Path>>(path: P, splats: &[SplatPoint]) -> Result<()> {
+    let f = File::create(path).context("Failed to create output file")?;
+    let mut writer = BufWriter::new(f);
+    write_splat_headered(&mut writer, splats)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Reads headered SPLAT data written by `write_splat_headered`.
+pub fn read_splat_headered(data: &[u8]) -> Result<Vec<SplatPoint>> {
+    if data.len() < SPLAT_HEADER_SIZE {
+        bail!("Invalid SPLAT data: {} bytes is too short", data.len());
+    }
+    if data[..4] != SPLAT_MAGIC {
+        bail!("Invalid SPLAT data: missing SPLT magic");
+    }
+    let word = |at: usize| u32::from_le_bytes(data[at..at + 4].try_into().unwrap());
+    let version = word(4);
+    if version != SPLAT_CONTAINER_VERSION {
+        bail!("Unsupported SPLAT container version {version}");
+    }
+    let count = word(8) as usize;
+    let records = &data[SPLAT_HEADER_SIZE..];
+    // Checked, as the product can overflow a 32-bit usize
+    if count.checked_mul(size_of::<SplatPoint>()) != Some(records.len()) {
+        bail!(
+            "Invalid SPLAT data: header announces {count} splats, but {} bytes follow",
+            records.len()
+        );
+    }
+    Ok(splats_from_bytes(records)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ply_to_splat, splats_to_bytes};
+
+    #[test]
+    fn test_round_trip() {
+        let points = (0..3)
+            .map(|i| crate::PlyGaussian {
+                x: i as f32,
+                opacity: i as f32,
+                rot_0: 1.0,
+                ..Default::default()
+            })
+            .collect();
+        let splats = ply_to_splat(points, true);
+
+        let mut data = Vec::new();
+        write_splat_headered(&mut data, &splats).unwrap();
+        assert_eq!(data.len(), SPLAT_HEADER_SIZE + 3 * 32);
+        assert_eq!(data[..4], *b"SPLT");
+        assert_eq!(data[8..12], 3u32.to_le_bytes());
+        assert_eq!(data[SPLAT_HEADER_SIZE..], splats_to_bytes(&splats));
+
+        let back = read_splat_headered(&data).unwrap();
+        assert_eq!(back.len(), 3);
+        assert_eq!(splats_to_bytes(&back), splats_to_bytes(&splats));
+
+        let err = read_splat_headered(&data[..data.len() - 32]).unwrap_err();
+        assert!(err.to_string().contains("announces 3 splats"));
+        assert!(read_splat_headered(&splats_to_bytes(&splats)).is_err());
+
+        // 2^27 splats of 32 bytes wrap to 0 in a 32-bit usize
+        let mut empty = data[..SPLAT_HEADER_SIZE].to_vec();
+        empty[8..12].copy_from_slice(&(1u32 << 27).to_le_bytes());
+        assert!(read_splat_headered(&empty).is_err());
+    }
+}
@@ -0,0 +1,210 @@
+//! Human-readable dumps of converted splats, for debugging conversions.
+//!
+//! Both formats hold the decoded `SplatPoint` fields as stored: scales are already
+//! exponentiated, colors and rotations are the quantized bytes.
+
+use crate::{SplatFormat, SplatPoint, splats_to_bytes_format, write_splat};
+use anyhow::{Context, Result};
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// File format of the converted output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum OutputFormat {
+    /// Binary 32-byte SPLAT records.
+    #[default]
+    Splat,
+    /// A JSON array with one object per splat.
+    Json,
+    /// CSV with a header row and one row per splat.
+    Csv,
+    /// Binary 20-byte records with half-float positions and scales (`SplatFormat::Compact16`).
+    Compact16,
+}
+
+impl OutputFormat {
+    /// Customary file extension, without the leading dot.
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Splat => "splat",
+            Self::Json => "json",
+            Self::Csv => "csv",
+            Self::Compact16 => "splat16",
+        }
+    }
+}
+
+/// Header row of the CSV dump.
+pub const CSV_HEADER: &str = "x,y,z,scale_x,scale_y,scale_z,r,g,b,a,rot_0,rot_1,rot_2,rot_3";
+
+/// Writes splats as CSV, with the columns of `CSV_HEADER`.
+pub fn write_splats_csv<W: Write>(writer: &mut W, splats: &[SplatPoint]) -> Result<()> {
+    writeln!(writer, "{CSV_HEADER}")?;
+    for s in splats {
+        let [x, y, z] = s.pos;
+        let [sx, sy, sz] = s.scale;
+        let [r, g, b, a] = s.color;
+        let [r0, r1, r2, r3] = s.rot;
+        writeln!(
+            writer,
+            "{x},{y},{z},{sx},{sy},{sz},{r},{g},{b},{a},{r0},{r1},{r2},{r3}"
+        )?;
+    }
+    Ok(())
+}
+
+/// Formats a float for JSON, which has no representation for NaN and infinities.
+fn json_number(out: &mut String, v: f32) {
+    if v.is_finite() {
+        let _ = write!(out, "{v}");
+    } else {
+        out.push_str("null");
+    }
+}
+
+/// Writes `s` as a quoted JSON string, escaping quotes, backslashes and control characters.
+pub(crate) fn json_string(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+fn json_array<T: Copy>(out: &mut String, values: [T; 3], mut item: impl FnMut(&mut String, T)) {
+    out.push('[');
+    for (i, v) in values.into_iter().enumerate() {
+        if i > 0 {
+            out.push_str(", ");
+        }
+        item(out, v);
+    }
+    out.push(']');
+}
+
+/// Writes splats as a JSON array of
+/// `{"position": [x, y, z], "scale": [x, y, z], "color": [r, g, b, a], "rotation": [r0, r1, r2, r3]}`
+/// objects, using the attribute names of the Python `Splat`. Non-finite floats become `null`.
+pub fn write_splats_json<W: Write>(writer: &mut W, splats: &[SplatPoint]) -> Result<()> {
+    writer.write_all(b"[")?;
+    let mut line = String::new();
+    for (i, s) in splats.iter().enumerate() {
+        line.clear();
+        line.push_str(if i > 0 { ",\n  " } else { "\n  " });
+        line.push_str("{\"position\": ");
+        json_array(&mut line, s.pos, json_number);
+        line.push_str(", \"scale\": ");
+        json_array(&mut line, s.scale, json_number);
+        let [r, g, b, a] = s.color;
+        let [r0, r1, r2, r3] = s.rot;
+        let _ = write!(
+            line,
+            ", \"color\": [{r}, {g}, {b}, {a}], \"rotation\": [{r0}, {r1}, {r2}, {r3}]}}"
+        );
+        writer.write_all(line.as_bytes())?;
+    }
+    writer.write_all(if splats.is_empty() { b"]\n" } else { b"\n]\n" })?;
+    Ok(())
+}
+
+/// Writes splats to `writer` in the given format.
+pub fn write_splats_as<W: Write>(
+    writer: &mut W,
+    splats: &[SplatPoint],
+    format: OutputFormat,
+) -> Result<()> {
+    match format {
+        OutputFormat::Splat => write_splat(writer, splats),
+        OutputFormat::Json => write_splats_json(writer, splats),
+        OutputFormat::Csv => write_splats_csv(writer, splats),
+        OutputFormat::Compact16 => {
+            writer.write_all(&splats_to_bytes_format(splats, SplatFormat::Compact16))?;
+            Ok(())
+        }
+    }
+}
+
+/// Saves splats to `path` in the given format.
+pub fn save_splats_as<P: AsRef<Path>>(
+    path: P,
+    splats: &[SplatPoint],
+    format: OutputFormat,
+) -> Result<()> {
+    let f = File::create(path).context("Failed to create output file")?;
+    let mut writer = BufWriter::new(f);
+    write_splats_as(&mut writer, splats, format).context("Failed to write output file")?;
+    writer.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{PlyGaussian, ply_to_splat};
+
+    fn splats() -> Vec<SplatPoint> {
+        let points = [0.5, -1.25]
+            .map(|x| PlyGaussian {
+                x,
+                rot_0: 1.0,
+                ..Default::default()
+            })
+            .to_vec();
+        ply_to_splat(points, false)
+    }
+
+    #[test]
+    fn test_csv() {
+        let mut out = Vec::new();
+        write_splats_csv(&mut out, &splats()).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], CSV_HEADER);
+        assert_eq!(lines[1], "0.5,0,0,1,1,1,127,127,127,127,255,128,128,128");
+        assert!(lines[2].starts_with("-1.25,"));
+    }
+
+    #[test]
+    fn test_json() {
+        let mut out = Vec::new();
+        write_splats_json(&mut out, &splats()).unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        let objects = json.as_array().unwrap();
+        assert_eq!(objects.len(), 2);
+        assert_eq!(objects[1]["position"][0], -1.25);
+        assert_eq!(
+            objects[0]["rotation"],
+            serde_json::json!([255, 128, 128, 128])
+        );
+
+        let mut empty = Vec::new();
+        write_splats_json(&mut empty, &[]).unwrap();
+        assert_eq!(
+            serde_json::from_slice::<serde_json::Value>(&empty).unwrap(),
+            serde_json::json!([])
+        );
+    }
+
+    #[test]
+    fn test_json_string_escapes() {
+        let name = "a\"b\\c\n\u{1}";
+        let mut out = String::new();
+        json_string(&mut out, name);
+        assert_eq!(out, "\"a\\\"b\\\\c\\n\\u0001\"");
+        assert_eq!(serde_json::from_str::<String>(&out).unwrap(), name);
+    }
+}
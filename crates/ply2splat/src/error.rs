@@ -0,0 +1,101 @@
+//! Typed errors and warnings of the public loaders.
+
+use crate::GAUSSIAN_PROPERTIES;
+use std::fmt;
+use std::sync::Arc;
+
+/// Error returned by the PLY loaders.
+///
+/// Implements `std::error::Error`, so it converts into `anyhow::Error` with `?`.
+#[derive(Debug, thiserror::Error)]
+pub enum Ply2SplatError {
+    /// The input could not be opened or read.
+    #[error("Failed to read PLY file: {0}")]
+    Io(#[from] std::io::Error),
+    /// The input is not a well-formed PLY file, or its payload does not match the header.
+    #[error("Failed to parse PLY {0}")]
+    Parse(String),
+    /// The header declares no element named `name`; `available` lists the elements it has.
+    #[error("PLY has no '{name}' element (available: {})", .available.join(", "))]
+    MissingElement {
+        name: String,
+        available: Vec<String>,
+    },
+    /// The Gaussian element (usually `vertex`) lacks some of the expected Gaussian properties, named in the
+    /// canonical order `x, y, z, f_dc_0, ..., rot_3`.
+    #[error(
+        "PLY vertex element is missing {} of {} expected properties: {}",
+        .0.len(),
+        GAUSSIAN_PROPERTIES.len(),
+        .0.join(", ")
+    )]
+    MissingProperties(Vec<String>),
+    /// The payload ends before the `declared` number of Gaussians; only `actual` were read.
+    /// Only returned with `LoadOptions::strict`.
+    #[error("PLY header declares {declared} vertices, but the payload holds only {actual}")]
+    CountMismatch { declared: usize, actual: usize },
+}
+
+/// Non-fatal problem found while loading, passed to `LoadOptions::on_warning`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LoadWarning {
+    /// A recognized Gaussian property is declared with a non-floating-point type, named in
+    /// `data_type`, and is read as 0.
+    UnsupportedPropertyType { property: String, data_type: String },
+    /// The payload ends before the `declared` number of Gaussians; the `actual` ones read are
+    /// kept. Without `LoadOptions::strict` only.
+    CountMismatch { declared: usize, actual: usize },
+    /// The property named by `LoadOptions::mask_property` does not exist, so nothing is masked.
+    MissingMaskProperty(String),
+}
+
+impl fmt::Display for LoadWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnsupportedPropertyType {
+                property,
+                data_type,
+            } => write!(
+                f,
+                "PLY property '{property}' has unsupported type {data_type} and will be read as 0"
+            ),
+            Self::CountMismatch { declared, actual } => write!(
+                f,
+                "PLY header declares {declared} vertices, but only {actual} could be read; \
+                 the file may be truncated"
+            ),
+            Self::MissingMaskProperty(name) => {
+                write!(f, "PLY has no '{name}' property; nothing will be masked")
+            }
+        }
+    }
+}
+
+/// Receives the `LoadWarning`s of a load, see `LoadOptions::on_warning`.
+///
+/// The default handler discards them, so the library never writes to stderr on its own.
+#[derive(Clone, Default)]
+pub struct WarningHandler(Option<Arc<WarningFn>>);
+
+type WarningFn = dyn Fn(&LoadWarning) + Send + Sync;
+
+impl WarningHandler {
+    /// Creates a handler calling `f` for every warning.
+    pub fn new(f: impl Fn(&LoadWarning) + Send + Sync + 'static) -> Self {
+        Self(Some(Arc::new(f)))
+    }
+
+    /// Passes `warning` to the handler, if any.
+    pub(crate) fn warn(&self, warning: LoadWarning) {
+        if let Some(f) = &self.0 {
+            f(&warning);
+        }
+    }
+}
+
+impl fmt::Debug for WarningHandler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let handler = if self.0.is_some() { "Some(..)" } else { "None" };
+        write!(f, "WarningHandler({handler})")
+    }
+}
@@ -0,0 +1,275 @@
+//! Reverse conversion from `SplatPoint`s back to Gaussian Splatting PLY data.
+//!
+//! The round trip PLY -> SPLAT -> PLY is lossy: positions and scales survive exactly (up to
+//! the `exp`/`ln` float error), but color, opacity and rotation were quantized to 8 bits.
+//! Quantized values are decoded to the center of their bucket to halve the worst-case error;
+//! the buckets depend on the `QuantizeMode` the splats were converted with.
+
+use crate::{GAUSSIAN_PROPERTIES, PlyGaussian, QuantizeMode, SH_C0, SplatPoint};
+use anyhow::{Context, Result};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// Decodes an 8-bit quantized value in `[0, 1]` to the center of its bucket under `mode`.
+///
+/// Truncation maps `[v, v + 1)` to `v`, rounding maps `[v - 0.5, v + 0.5)` to `v`.
+fn dequantize_unit(v: u8, mode: QuantizeMode) -> f32 {
+    match mode {
+        QuantizeMode::Truncate => ((v as f32 + 0.5) / 255.0).min(1.0),
+        QuantizeMode::Round => v as f32 / 255.0,
+    }
+}
+
+impl PlyGaussian {
+    /// Reconstructs a raw `PlyGaussian` from a processed `SplatPoint`.
+    ///
+    /// This inverts `SplatPoint::from_ply`: opacity goes back through the logit, scales through
+    /// `ln`, colors through the SH DC relation and the rotation is decoded from `[0, 255]`
+    /// to `[-1, 1]` and re-normalized. Assumes the default `QuantizeMode::Truncate`; see
+    /// `from_splat_with_mode`.
+    pub fn from_splat(s: &SplatPoint) -> Self {
+        Self::from_splat_with_mode(s, QuantizeMode::Truncate)
+    }
+
+    /// Reconstructs a raw `PlyGaussian` from a `SplatPoint` converted with `mode`.
+    ///
+    /// Decoding with the mode used for conversion makes re-converting the result reproduce
+    /// the same color bytes.
+    pub fn from_splat_with_mode(s: &SplatPoint, mode: QuantizeMode) -> Self {
+        let [f_dc_0, f_dc_1, f_dc_2] =
+            [s.color[0], s.color[1], s.color[2]].map(|c| (dequantize_unit(c, mode) - 0.5) / SH_C0);
+
+        // Keep alpha strictly inside (0, 1) so the logit stays finite.
+        let alpha = dequantize_unit(s.color[3], mode).clamp(1e-6, 1.0 - 1e-6);
+        let opacity = (alpha / (1.0 - alpha)).ln();
+
+        let rot = s.rot.map(|r| (r as f32 - 128.0) / 128.0);
+        let q_len = rot.iter().map(|r| r * r).sum::<f32>().sqrt();
+        let [rot_0, rot_1, rot_2, rot_3] = if q_len > 0.0 {
+            rot.map(|r| r / q_len)
+        } else {
+            [1.0, 0.0, 0.0, 0.0]
+        };
+
+        PlyGaussian {
+            x: s.pos[0],
+            y: s.pos[1],
+            z: s.pos[2],
+            f_dc_0,
+            f_dc_1,
+            f_dc_2,
+            opacity,
+            scale_0: s.scale[0].ln(),
+            scale_1: s.scale[1].ln(),
+            scale_2: s.scale[2].ln(),
+            rot_0,
+            rot_1,
+            rot_2,
+            rot_3,
+            ..Default::default()
+        }
+    }
+
+    /// Values of the standard properties in `GAUSSIAN_PROPERTIES` order.
+    fn standard_values(&self) -> [f32; 14] {
+        [
+            self.x,
+            self.y,
+            self.z,
+            self.f_dc_0,
+            self.f_dc_1,
+            self.f_dc_2,
+            self.opacity,
+            self.scale_0,
+            self.scale_1,
+            self.scale_2,
+            self.rot_0,
+            self.rot_1,
+            self.rot_2,
+            self.rot_3,
+        ]
+    }
+}
+
+/// Converts processed splats back into raw PLY Gaussians.
+///
+/// See the module documentation for the precision of the round trip. Assumes the splats
+/// were converted with the default `QuantizeMode::Truncate`; see `splat_to_ply_with_mode`.
+pub fn splat_to_ply(splats: &[SplatPoint]) -> Vec<PlyGaussian> {
+    splat_to_ply_with_mode(splats, QuantizeMode::Truncate)
+}
+
+/// Converts splats produced with `ConvertOptions::quantize == mode` back into raw PLY
+/// Gaussians.
+pub fn splat_to_ply_with_mode(splats: &[SplatPoint], mode: QuantizeMode) -> Vec<PlyGaussian> {
+    splats
+        .iter()
+        .map(|s| PlyGaussian::from_splat_with_mode(s, mode))
+        .collect()
+}
+
+/// Writes the PLY header declaring `count` vertices with the 14 standard float properties.
+fn write_header<W: Write>(writer: &mut W, format: &str, count: usize) -> std::io::Result<()> {
+    writeln!(writer, "ply")?;
+    writeln!(writer, "format {format} 1.0")?;
+    writeln!(writer, "element vertex {count}")?;
+    for name in GAUSSIAN_PROPERTIES {
+        writeln!(writer, "property float {name}")?;
+    }
+    writeln!(writer, "end_header")
+}
+
+/// Writes Gaussians as an ASCII PLY with the standard Gaussian Splatting properties.
+///
+/// # Arguments
+/// * `writer` - Destination for the PLY data.
+/// * `gaussians` - The Gaussians to write.
+pub fn write_ply_ascii<W: Write>(writer: &mut W, gaussians: &[PlyGaussian]) -> Result<()> {
+    write_header(writer, "ascii", gaussians.len())?;
+    for g in gaussians {
+        let line = g.standard_values().map(|v| v.to_string()).join(" ");
+        writeln!(writer, "{line}")?;
+    }
+    Ok(())
+}
+
+/// Writes Gaussians as a binary little-endian PLY with the standard Gaussian Splatting properties.
+///
+/// # Arguments
+/// * `writer` - Destination for the PLY data.
+/// * `gaussians` - The Gaussians to write.
+pub fn write_ply_binary<W: Write>(writer: &mut W, gaussians: &[PlyGaussian]) -> Result<()> {
+    write_header(writer, "binary_little_endian", gaussians.len())?;
+    for g in gaussians {
+        for v in g.standard_values() {
+            writer.write_all(&v.to_le_bytes())?;
+        }
+    }
+    Ok(())
+}
+
+/// Saves Gaussians to a binary little-endian PLY file.
+///
+/// # Arguments
+/// * `path` - Destination path.
+/// * `gaussians` - The Gaussians to write.
+pub fn save_ply<P: AsRef<Path>>(path: P, gaussians: &[PlyGaussian]) -> Result<()> {
+    let f = File::create(path).context("Failed to create output file")?;
+    let mut writer = BufWriter::new(f);
+    write_ply_binary(&mut writer, gaussians).context("Failed to write PLY data")?;
+    writer.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ConvertOptions, load_ply_from_bytes, ply_to_splat, ply_to_splat_with_options};
+
+    fn sigmoid(x: f32) -> f32 {
+        1.0 / (1.0 + (-x).exp())
+    }
+
+    fn sample() -> Vec<PlyGaussian> {
+        vec![
+            PlyGaussian {
+                x: 1.25,
+                y: -2.5,
+                z: 3.0,
+                f_dc_0: 0.4,
+                f_dc_1: -0.3,
+                f_dc_2: 0.0,
+                opacity: 1.5,
+                scale_0: -3.0,
+                scale_1: -2.0,
+                scale_2: 0.5,
+                rot_0: 0.9,
+                rot_1: 0.1,
+                rot_2: -0.3,
+                rot_3: 0.2,
+                ..Default::default()
+            },
+            PlyGaussian {
+                x: -7.0,
+                opacity: -2.0,
+                rot_0: 1.0,
+                ..Default::default()
+            },
+        ]
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let original = sample();
+        let splats = ply_to_splat(original.clone(), false);
+        let restored = splat_to_ply(&splats);
+
+        for (o, r) in original.iter().zip(&restored) {
+            assert_eq!([o.x, o.y, o.z], [r.x, r.y, r.z]);
+            assert!((o.scale_0 - r.scale_0).abs() < 1e-5);
+            assert!((o.scale_1 - r.scale_1).abs() < 1e-5);
+            assert!((o.scale_2 - r.scale_2).abs() < 1e-5);
+            // Alpha is quantized to 8 bits
+            assert!((sigmoid(o.opacity) - sigmoid(r.opacity)).abs() <= 1.0 / 255.0);
+            // Color is quantized to 8 bits
+            assert!((SH_C0 * (o.f_dc_0 - r.f_dc_0)).abs() <= 1.0 / 255.0);
+        }
+
+        // Re-converting the restored Gaussians reproduces the same quantized bytes.
+        let again = ply_to_splat(restored, false);
+        for (a, b) in splats.iter().zip(&again) {
+            assert_eq!(a.color, b.color);
+            assert_eq!(a.pos, b.pos);
+        }
+    }
+
+    #[test]
+    fn test_round_trip_every_color_byte() {
+        // f32 PLY -> SPLAT -> PLY -> SPLAT keeps every color and alpha byte in both modes
+        let gaussians: Vec<PlyGaussian> = (0..=255u8)
+            .map(|v| {
+                let c = v as f32 / 255.0;
+                PlyGaussian {
+                    f_dc_0: (c - 0.5) / SH_C0,
+                    f_dc_1: (c * 0.7 - 0.5) / SH_C0,
+                    opacity: (c.clamp(0.01, 0.99) / (1.0 - c.clamp(0.01, 0.99))).ln(),
+                    rot_0: 1.0,
+                    ..Default::default()
+                }
+            })
+            .collect();
+        for quantize in [QuantizeMode::Truncate, QuantizeMode::Round] {
+            let options = ConvertOptions {
+                sort: false,
+                quantize,
+                ..Default::default()
+            };
+            let splats = ply_to_splat_with_options(gaussians.clone(), &options);
+            let restored = splat_to_ply_with_mode(&splats, quantize);
+            let again = ply_to_splat_with_options(restored, &options);
+            for (a, b) in splats.iter().zip(&again) {
+                assert_eq!(a.color, b.color, "{quantize:?}");
+                assert_eq!(a.rot, b.rot, "{quantize:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_write_ply_ascii_and_binary_parse_back() {
+        let gaussians = sample();
+
+        let mut ascii = Vec::new();
+        write_ply_ascii(&mut ascii, &gaussians).unwrap();
+        let mut binary = Vec::new();
+        write_ply_binary(&mut binary, &gaussians).unwrap();
+
+        for data in [ascii, binary] {
+            let parsed = load_ply_from_bytes(&data).unwrap();
+            assert_eq!(parsed.len(), 2);
+            assert_eq!(parsed[0].x, 1.25);
+            assert_eq!(parsed[0].rot_2, -0.3);
+            assert_eq!(parsed[1].opacity, -2.0);
+        }
+    }
+}
@@ -0,0 +1,229 @@
+//! Extended SPLAT format carrying per-splat attributes beyond the 32-byte core.
+//!
+//! ## Layout
+//!
+//! All integers are little-endian. For `n` splats:
+//!
+//! | Offset            | Size       | Content                                          |
+//! |-------------------|------------|--------------------------------------------------|
+//! | 0                 | `n * 32`   | Core `SplatPoint` records, identical to `.splat` |
+//! | `n * 32`          | `n * 2`    | Confidence as `f16` (only if `FLAG_CONFIDENCE`)  |
+//! | ...               | 0 or 2     | Zero padding to a multiple of 4 bytes            |
+//! | end - 16          | 4          | Flags (`u32`)                                    |
+//! | end - 12          | 4          | Splat count `n` (`u32`)                          |
+//! | end - 8           | 4          | Format version (`u32`, currently 1)              |
+//! | end - 4           | 4          | Magic `SPLX`                                     |
+//!
+//! The core comes first and is unchanged, so readers that only understand the standard format
+//! can load the first `n * 32` bytes and ignore the extra bytes. Viewers that derive the splat
+//! count from the file size should be given the plain `.splat` output instead.
+
+use crate::{ConvertOptions, PlyGaussian, SplatPoint, convert_keyed, in_thread_pool};
+use anyhow::{Context, Result, bail};
+use half::f16;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// Magic bytes at the very end of an extended SPLAT file.
+pub const EXTENDED_MAGIC: [u8; 4] = *b"SPLX";
+/// Current version of the extended format.
+pub const EXTENDED_VERSION: u32 = 1;
+/// Flag bit set when the file carries a confidence section.
+pub const FLAG_CONFIDENCE: u32 = 1;
+
+const TRAILER_SIZE: usize = 16;
+
+/// Splats together with their optional per-splat attributes.
+#[derive(Debug, Clone, Default)]
+pub struct ExtendedSplats {
+    /// The core splat records.
+    pub splats: Vec<SplatPoint>,
+    /// Per-splat confidence, in the same order as `splats`.
+    pub confidence: Option<Vec<f32>>,
+}
+
+/// Converts Gaussians like `ply_to_splat_with_options`, keeping each Gaussian's
+/// `confidence` attached to its splat through filtering and sorting.
+pub fn ply_to_splat_extended(
+    ply_points: Vec<PlyGaussian>,
+    options: &ConvertOptions,
+) -> ExtendedSplats {
+    let (splats, confidence) = in_thread_pool(options.num_threads, || {
+        convert_keyed(ply_points, options, |_, p| p.confidence, None)
+            .into_iter()
+            .map(|((s, _), c)| (s, c))
+            .unzip()
+    });
+    ExtendedSplats {
+        splats,
+        confidence: Some(confidence),
+    }
+}
+
+/// Writes splats in the extended format described in the module documentation.
+pub fn write_extended_splats<W: Write>(writer: &mut W, data: &ExtendedSplats) -> Result<()> {
+    let count = data.splats.len();
+    writer.write_all(bytemuck::cast_slice(&data.splats))?;
+
+    let mut flags = 0;
+    if let Some(confidence) = &data.confidence {
+        if confidence.len() != count {
+            bail!(
+                "Confidence has {} values but there are {} splats",
+                confidence.len(),
+                count
+            );
+        }
+        flags |= FLAG_CONFIDENCE;
+        for c in confidence {
+            writer.write_all(&f16::from_f32(*c).to_le_bytes())?;
+        }
+        if count % 2 == 1 {
+            writer.write_all(&[0, 0])?;
+        }
+    }
+
+    writer.write_all(&flags.to_le_bytes())?;
+    writer.write_all(&(count as u32).to_le_bytes())?;
+    writer.write_all(&EXTENDED_VERSION.to_le_bytes())?;
+    writer.write_all(&EXTENDED_MAGIC)?;
+    Ok(())
+}
+
+/// Saves splats to a file in the extended format.
+pub fn save_splat_extended<P: AsRef<Path>>(path: P, data: &ExtendedSplats) -> Result<()> {
+    let f = File::create(path).context("Failed to create output file")?;
+    let mut writer = BufWriter::new(f);
+    write_extended_splats(&mut writer, data)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Reads data written by `write_extended_splats`.
+///
+/// Confidence values come back with `f16` precision.
+pub fn read_extended_splats(data: &[u8]) -> Result<ExtendedSplats> {
+    let Some((body, trailer)) = data.split_last_chunk::<TRAILER_SIZE>() else {
+        bail!(
+            "Invalid extended SPLAT data: {} bytes is too short",
+            data.len()
+        );
+    };
+    let word = |i: usize| u32::from_le_bytes(trailer[i * 4..i * 4 + 4].try_into().unwrap());
+    if trailer[12..] != EXTENDED_MAGIC {
+        bail!("Invalid extended SPLAT data: missing SPLX magic");
+    }
+    let (flags, count, version) = (word(0), word(1) as usize, word(2));
+    if version != EXTENDED_VERSION {
+        bail!("Unsupported extended SPLAT version {version}");
+    }
+
+    // Checked, as the sizes can overflow a 32-bit usize
+    let core_len = count.checked_mul(size_of::<SplatPoint>());
+    let confidence_len = if flags & FLAG_CONFIDENCE != 0 {
+        count
+            .checked_mul(2)
+            .and_then(|len| len.checked_next_multiple_of(4))
+    } else {
+        Some(0)
+    };
+    let Some((core_len, needed)) = core_len
+        .zip(confidence_len)
+        .and_then(|(core, confidence)| Some((core, core.checked_add(confidence)?)))
+    else {
+        bail!("Invalid extended SPLAT data: {count} splats do not fit in memory");
+    };
+    if body.len() != needed {
+        bail!(
+            "Invalid extended SPLAT data: {} splats need {} bytes before the trailer, found {}",
+            count,
+            needed,
+            body.len()
+        );
+    }
+
+    let (core, rest) = body.split_at(core_len);
+    let splats = core
+        .chunks_exact(size_of::<SplatPoint>())
+        .map(bytemuck::pod_read_unaligned)
+        .collect();
+    let confidence = (flags & FLAG_CONFIDENCE != 0).then(|| {
+        rest.chunks_exact(2)
+            .take(count)
+            .map(|b| f16::from_le_bytes([b[0], b[1]]).to_f32())
+            .collect()
+    });
+    Ok(ExtendedSplats { splats, confidence })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::splats_to_bytes;
+
+    fn scene() -> Vec<PlyGaussian> {
+        [(0.0, -2.0, 0.25), (1.0, 3.0, 0.9), (2.0, 0.5, 0.003)]
+            .into_iter()
+            .map(|(x, opacity, confidence)| PlyGaussian {
+                x,
+                opacity,
+                confidence,
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_confidence_round_trip() {
+        let extended = ply_to_splat_extended(scene(), &ConvertOptions::default());
+        // Sorting moved the confidence along with its splat
+        let by_x: Vec<(f32, f32)> = extended
+            .splats
+            .iter()
+            .zip(extended.confidence.as_ref().unwrap())
+            .map(|(s, c)| (s.pos[0], *c))
+            .collect();
+        assert_eq!(by_x, [(1.0, 0.9), (2.0, 0.003), (0.0, 0.25)]);
+
+        let mut bytes = Vec::new();
+        write_extended_splats(&mut bytes, &extended).unwrap();
+        // The core section is a standard SPLAT stream
+        assert_eq!(&bytes[..96], splats_to_bytes(&extended.splats));
+        assert_eq!(bytes.len(), 96 + 8 + TRAILER_SIZE);
+
+        let read = read_extended_splats(&bytes).unwrap();
+        assert_eq!(
+            splats_to_bytes(&read.splats),
+            splats_to_bytes(&extended.splats)
+        );
+        for (a, b) in read
+            .confidence
+            .unwrap()
+            .iter()
+            .zip(extended.confidence.unwrap())
+        {
+            assert!((a - b).abs() <= b.abs() * 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_without_confidence() {
+        let data = ExtendedSplats {
+            splats: crate::ply_to_splat(scene(), false),
+            confidence: None,
+        };
+        let mut bytes = Vec::new();
+        write_extended_splats(&mut bytes, &data).unwrap();
+        let read = read_extended_splats(&bytes).unwrap();
+        assert_eq!(read.splats.len(), 3);
+        assert!(read.confidence.is_none());
+
+        assert!(read_extended_splats(&bytes[1..]).is_err());
+
+        // 2^27 splats of 32 bytes wrap to 0 in a 32-bit usize
+        let mut trailer = bytes[bytes.len() - TRAILER_SIZE..].to_vec();
+        trailer[4..8].copy_from_slice(&(1u32 << 27).to_le_bytes());
+        assert!(read_extended_splats(&trailer).is_err());
+    }
+}
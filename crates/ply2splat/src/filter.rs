@@ -0,0 +1,135 @@
+//! Dropping near-transparent and oversized Gaussians during conversion.
+
+use crate::{ActivationMode, ConvertOptions, PlyGaussian, SplatPoint, ply_to_splat_with_options};
+
+/// Thresholds for discarding Gaussians before they are converted.
+///
+/// The default keeps every Gaussian.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SplatFilter {
+    /// Minimum opacity after the sigmoid activation, in `[0, 1]`.
+    pub min_opacity: f32,
+    /// Maximum size of the largest axis after the exponential activation.
+    pub max_scale: f32,
+}
+
+impl Default for SplatFilter {
+    fn default() -> Self {
+        Self {
+            min_opacity: 0.0,
+            max_scale: f32::INFINITY,
+        }
+    }
+}
+
+impl SplatFilter {
+    /// Returns whether `p` passes both thresholds.
+    pub fn keeps(&self, p: &PlyGaussian) -> bool {
+        self.keeps_as(p, ActivationMode::Raw)
+    }
+
+    /// Returns whether `p`, stored as described by `activation`, passes both thresholds.
+    pub fn keeps_as(&self, p: &PlyGaussian, activation: ActivationMode) -> bool {
+        let alpha = activation.opacity(p);
+        let [s0, s1, s2] = activation.scales(p);
+        let largest = s0.max(s1).max(s2);
+        !(alpha < self.min_opacity || largest > self.max_scale)
+    }
+}
+
+/// Converts Gaussians into splats, dropping those rejected by `filter`.
+///
+/// Filtering happens in the same parallel pass as the conversion, so the result only
+/// contains the surviving splats.
+///
+/// # Arguments
+/// * `ply_points` - A vector of raw `PlyGaussian` data.
+/// * `sort` - If true, sorts the splats by importance (volume * opacity).
+/// * `filter` - Opacity and scale thresholds.
+pub fn ply_to_splat_filtered(
+    ply_points: Vec<PlyGaussian>,
+    sort: bool,
+    filter: &SplatFilter,
+) -> Vec<SplatPoint> {
+    ply_to_splat_with_options(
+        ply_points,
+        &ConvertOptions {
+            sort,
+            filter: *filter,
+            ..Default::default()
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ply_to_splat, splats_to_bytes};
+
+    fn scene() -> Vec<PlyGaussian> {
+        vec![
+            // alpha ~0.05
+            PlyGaussian {
+                x: 0.0,
+                opacity: -3.0,
+                ..Default::default()
+            },
+            // alpha 0.5, largest scale e^3 ~20
+            PlyGaussian {
+                x: 1.0,
+                opacity: 0.0,
+                scale_1: 3.0,
+                ..Default::default()
+            },
+            // alpha ~0.95, scale 1
+            PlyGaussian {
+                x: 2.0,
+                opacity: 3.0,
+                ..Default::default()
+            },
+        ]
+    }
+
+    fn xs(splats: &[SplatPoint]) -> Vec<f32> {
+        splats.iter().map(|s| s.pos[0]).collect()
+    }
+
+    #[test]
+    fn test_min_opacity() {
+        let filter = SplatFilter {
+            min_opacity: 0.1,
+            ..Default::default()
+        };
+        assert_eq!(
+            xs(&ply_to_splat_filtered(scene(), false, &filter)),
+            [1.0, 2.0]
+        );
+    }
+
+    #[test]
+    fn test_max_scale() {
+        let filter = SplatFilter {
+            max_scale: 10.0,
+            ..Default::default()
+        };
+        assert_eq!(
+            xs(&ply_to_splat_filtered(scene(), false, &filter)),
+            [0.0, 2.0]
+        );
+
+        let both = SplatFilter {
+            min_opacity: 0.1,
+            max_scale: 10.0,
+        };
+        assert_eq!(xs(&ply_to_splat_filtered(scene(), true, &both)), [2.0]);
+    }
+
+    #[test]
+    fn test_default_filters_nothing() {
+        let filtered = ply_to_splat_filtered(scene(), true, &SplatFilter::default());
+        assert_eq!(
+            splats_to_bytes(&filtered),
+            splats_to_bytes(&ply_to_splat(scene(), true))
+        );
+    }
+}
@@ -0,0 +1,191 @@
+//! Count-prefixed framing of splat data for message-based transports such as WebSockets.
+//!
+//! ## Frame layout
+//!
+//! Every frame is a self-contained binary message:
+//!
+//! | Offset | Size           | Content                                  |
+//! |--------|----------------|------------------------------------------|
+//! | 0      | 4              | Splat count `n` (`u32`, little-endian)   |
+//! | 4      | `n * 32`       | `n` raw 32-byte `SplatPoint` records     |
+//!
+//! A client appends the records of each frame to its buffer in arrival order; concatenating
+//! the payloads of all frames yields exactly the output of `splats_to_bytes`.
+
+use crate::SplatPoint;
+use anyhow::{Result, bail};
+
+/// Size of the count prefix in bytes.
+pub const FRAME_HEADER_SIZE: usize = 4;
+
+/// Encodes a single frame containing `splats`.
+pub fn encode_frame(splats: &[SplatPoint]) -> Vec<u8> {
+    let payload: &[u8] = bytemuck::cast_slice(splats);
+    let mut frame = Vec::with_capacity(FRAME_HEADER_SIZE + payload.len());
+    frame.extend_from_slice(&(splats.len() as u32).to_le_bytes());
+    frame.extend_from_slice(payload);
+    frame
+}
+
+/// Decodes a frame produced by `encode_frame` or `SplatFrameWriter`.
+///
+/// # Returns
+/// The splats contained in the frame, or an error if the frame is truncated or its
+/// length does not match the declared count.
+pub fn decode_frame(frame: &[u8]) -> Result<Vec<SplatPoint>> {
+    let Some((header, payload)) = frame.split_first_chunk::<FRAME_HEADER_SIZE>() else {
+        bail!("Invalid frame: {} bytes is too short", frame.len());
+    };
+    let count = u32::from_le_bytes(*header) as usize;
+    // Checked, as the product can overflow a 32-bit usize
+    let Some(expected) = count.checked_mul(size_of::<SplatPoint>()) else {
+        bail!("Invalid frame: declares {count} splats, more than fit in memory");
+    };
+    if payload.len() != expected {
+        bail!(
+            "Invalid frame: declares {} splats ({} bytes) but carries {} bytes",
+            count,
+            expected,
+            payload.len()
+        );
+    }
+    // The payload is not necessarily 4-byte aligned, so copy instead of casting in place.
+    Ok(payload
+        .chunks_exact(size_of::<SplatPoint>())
+        .map(bytemuck::pod_read_unaligned)
+        .collect())
+}
+
+/// Iterator over the frames of a splat slice, see `splat_frames`.
+pub struct SplatFrames<'a> {
+    chunks: std::slice::Chunks<'a, SplatPoint>,
+}
+
+impl Iterator for SplatFrames<'_> {
+    type Item = Vec<u8>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.chunks.next().map(encode_frame)
+    }
+}
+
+/// Splits `splats` into frames of at most `splats_per_frame` splats each.
+///
+/// # Panics
+/// Panics if `splats_per_frame` is 0.
+pub fn splat_frames(splats: &[SplatPoint], splats_per_frame: usize) -> SplatFrames<'_> {
+    SplatFrames {
+        chunks: splats.chunks(splats_per_frame),
+    }
+}
+
+/// Incrementally groups a stream of splats into frames and hands each finished frame to a sink.
+///
+/// The sink is typically a closure that sends a binary WebSocket message. Splats can be written
+/// in batches of any size; a frame is emitted whenever `splats_per_frame` splats are buffered,
+/// and `finish` emits the remaining partial frame.
+pub struct SplatFrameWriter<F: FnMut(Vec<u8>) -> Result<()>> {
+    splats_per_frame: usize,
+    pending: Vec<SplatPoint>,
+    sink: F,
+}
+
+impl<F: FnMut(Vec<u8>) -> Result<()>> SplatFrameWriter<F> {
+    /// Creates a writer emitting frames of `splats_per_frame` splats into `sink`.
+    ///
+    /// # Panics
+    /// Panics if `splats_per_frame` is 0.
+    pub fn new(splats_per_frame: usize, sink: F) -> Self {
+        assert!(splats_per_frame > 0, "splats_per_frame must be positive");
+        Self {
+            splats_per_frame,
+            pending: Vec::with_capacity(splats_per_frame),
+            sink,
+        }
+    }
+
+    /// Buffers `splats`, emitting every frame that becomes full.
+    pub fn write_splats(&mut self, mut splats: &[SplatPoint]) -> Result<()> {
+        while !splats.is_empty() {
+            let take = (self.splats_per_frame - self.pending.len()).min(splats.len());
+            self.pending.extend_from_slice(&splats[..take]);
+            splats = &splats[take..];
+            if self.pending.len() == self.splats_per_frame {
+                self.emit()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Emits the remaining buffered splats as a final, possibly shorter frame.
+    pub fn finish(mut self) -> Result<()> {
+        if !self.pending.is_empty() {
+            self.emit()?;
+        }
+        Ok(())
+    }
+
+    fn emit(&mut self) -> Result<()> {
+        let frame = encode_frame(&self.pending);
+        self.pending.clear();
+        (self.sink)(frame)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{PlyGaussian, ply_to_splat, splats_to_bytes};
+
+    fn scene(n: usize) -> Vec<SplatPoint> {
+        let points = (0..n)
+            .map(|i| PlyGaussian {
+                x: i as f32,
+                opacity: (i % 4) as f32,
+                ..Default::default()
+            })
+            .collect();
+        ply_to_splat(points, true)
+    }
+
+    #[test]
+    fn test_frames_reassemble() {
+        let splats = scene(10);
+        let frames: Vec<Vec<u8>> = splat_frames(&splats, 4).collect();
+        assert_eq!(frames.len(), 3);
+        assert_eq!(&frames[2][..4], &2u32.to_le_bytes());
+
+        let mut reassembled = Vec::new();
+        for frame in &frames {
+            reassembled.extend(decode_frame(frame).unwrap());
+        }
+        assert_eq!(splats_to_bytes(&reassembled), splats_to_bytes(&splats));
+    }
+
+    #[test]
+    fn test_frame_writer_matches_iterator() {
+        let splats = scene(11);
+        let mut frames = Vec::new();
+        let mut writer = SplatFrameWriter::new(3, |frame| {
+            frames.push(frame);
+            Ok(())
+        });
+        for batch in splats.chunks(5) {
+            writer.write_splats(batch).unwrap();
+        }
+        writer.finish().unwrap();
+
+        let expected: Vec<Vec<u8>> = splat_frames(&splats, 3).collect();
+        assert_eq!(frames, expected);
+    }
+
+    #[test]
+    fn test_decode_frame_invalid() {
+        assert!(decode_frame(&[1, 0]).is_err());
+        let mut frame = encode_frame(&scene(2));
+        frame.pop();
+        assert!(decode_frame(&frame).is_err());
+        // 2^27 splats of 32 bytes wrap to 0 in a 32-bit usize
+        assert!(decode_frame(&(1u32 << 27).to_le_bytes()).is_err());
+    }
+}
@@ -0,0 +1,116 @@
+//! Header-only inspection, to check a file before committing to a long conversion.
+
+use crate::{Ply2SplatError, validate_gaussian_header};
+use ply_rs::parser::Parser;
+use ply_rs::ply::{DefaultElement, Encoding, Header};
+use std::fmt;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Cursor};
+use std::path::Path;
+
+/// Summary of a PLY header.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlyInfo {
+    /// Payload encoding: `ascii`, `binary_little_endian` or `binary_big_endian`.
+    pub format: &'static str,
+    /// Names and declared counts of all elements, in header order.
+    pub elements: Vec<(String, usize)>,
+    /// Declared number of vertices, 0 without a `vertex` element.
+    pub vertex_count: usize,
+    /// Properties of the `vertex` element, in header order.
+    pub properties: Vec<String>,
+    /// Expected Gaussian properties absent from the `vertex` element.
+    pub missing: Vec<String>,
+}
+
+impl PlyInfo {
+    fn from_header(header: &Header) -> Self {
+        let vertex = header.elements.get("vertex");
+        Self {
+            format: match header.encoding {
+                Encoding::Ascii => "ascii",
+                Encoding::BinaryLittleEndian => "binary_little_endian",
+                Encoding::BinaryBigEndian => "binary_big_endian",
+            },
+            elements: header
+                .elements
+                .values()
+                .map(|e| (e.name.clone(), e.count))
+                .collect(),
+            vertex_count: vertex.map_or(0, |v| v.count),
+            properties: vertex.map_or_else(Vec::new, |v| v.properties.keys().cloned().collect()),
+            missing: match validate_gaussian_header(header) {
+                Err(Ply2SplatError::MissingProperties(missing)) => missing,
+                _ => Vec::new(),
+            },
+        }
+    }
+}
+
+impl fmt::Display for PlyInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Format: {}", self.format)?;
+        for (name, count) in &self.elements {
+            writeln!(f, "Element '{name}': {count}")?;
+        }
+        writeln!(f, "Vertices: {}", self.vertex_count)?;
+        writeln!(f, "Vertex properties: {}", self.properties.join(", "))?;
+        if self.missing.is_empty() {
+            write!(f, "All expected Gaussian properties present")
+        } else {
+            write!(f, "Missing properties: {}", self.missing.join(", "))
+        }
+    }
+}
+
+fn inspect<R: BufRead>(reader: &mut R, source: &str) -> Result<PlyInfo, Ply2SplatError> {
+    let header = Parser::<DefaultElement>::new()
+        .read_header(reader)
+        .map_err(|e| Ply2SplatError::Parse(format!("{source}: {e}")))?;
+    Ok(PlyInfo::from_header(&header))
+}
+
+/// Reads only the header of a PLY file; the payload is never read.
+///
+/// A file without a `vertex` element is reported with `vertex_count` 0 rather than failing.
+pub fn inspect_ply<P: AsRef<Path>>(path: P) -> Result<PlyInfo, Ply2SplatError> {
+    let f = File::open(path)?;
+    inspect(&mut BufReader::new(f), "file")
+}
+
+/// Reads only the header of in-memory PLY data.
+pub fn inspect_ply_bytes(data: &[u8]) -> Result<PlyInfo, Ply2SplatError> {
+    inspect(&mut Cursor::new(data), "data")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inspect() {
+        let ply = b"ply
+format ascii 1.0
+comment truncated payload, which inspection never reads
+element vertex 3
+property float x
+property float y
+property float z
+property float opacity
+element face 1
+property list uchar int vertex_indices
+end_header
+0 0 0 1
+";
+        let info = inspect_ply_bytes(ply).unwrap();
+        assert_eq!(info.vertex_count, 3);
+        assert_eq!(info.format, "ascii");
+        assert_eq!(
+            info.elements,
+            [("vertex".to_string(), 3), ("face".to_string(), 1)]
+        );
+        assert_eq!(info.properties, ["x", "y", "z", "opacity"]);
+        assert_eq!(info.missing.len(), 10);
+        assert!(!info.missing.contains(&"opacity".to_string()));
+    }
+}
@@ -5,28 +5,240 @@
 //!
 //! ## Features
 //!
-//! - **Fast Parsing**: Uses `ply-rs` for robust PLY parsing.
+//! - **Fast Parsing**: Uses `ply-rs` for robust PLY parsing, plus a selective binary reader
+//!   (`load_ply_from_bytes_selective`) that skips unused properties.
+//! - **Memory-Mapped Loading** (`mmap` feature, enabled by default): `load_ply_mmap` decodes
+//!   large binary PLYs straight from a mapping of the file.
+//! - **Header Validation**: loaders reject `vertex` elements lacking any of the expected
+//!   Gaussian properties with `Ply2SplatError::MissingProperties`, unless
+//!   `LoadOptions::allow_missing` is set. `LoadOptions::element` reads Gaussians stored under
+//!   another element name, such as `point`.
+//! - **Typed Errors**: the loaders return `Ply2SplatError`, so callers can tell I/O, parse
+//!   and header errors apart without inspecting messages. Non-fatal problems such as a
+//!   truncated payload are passed as `LoadWarning`s to `LoadOptions::on_warning` instead of
+//!   being printed.
 //! - **Parallel Processing**: Leverages `rayon` for multi-threaded conversion and sorting
 //!   (when the `parallel` feature is enabled).
 //! - **Optimized Output**: Produces a dense, memory-efficient binary format (32 bytes per splat).
+//! - **Headered Output**: `save_splat_headered` prefixes the records with a magic, version and
+//!   splat count (`SplatContainer::Headered`), read back by `read_splat_headered`.
+//! - **Compact Output**: `splats_to_bytes_format` with `SplatFormat::Compact16` writes 20-byte
+//!   records with half-float positions and scales; readers must use the 20-byte stride.
+//! - **Progress**: `ply_to_splat_with_progress` reports `(processed, total)` while converting.
 //! - **Sorting**: Automatically sorts splats by importance (volume * opacity) and spatial position
-//!   for deterministic rendering order.
+//!   for deterministic rendering order; `SortKey` selects opacity, camera distance or Morton
+//!   (Z-order) locality instead.
+//!   `ply_to_splat_with_permutation` also returns the original vertex index of every splat.
+//! - **Color LUT**: `ColorLut` remaps the quantized color bytes to a custom response curve.
+//! - **Editing**: `SplatBuilder` creates splats from explicit values or edits converted ones
+//!   before serializing them again.
+//! - **Reverse Conversion**: `splat_to_ply` and `save_ply` turn splats back into a (lossy)
+//!   Gaussian Splatting PLY; `PlySchema` captures the original header so the regenerated
+//!   file keeps its property names, types and order.
+//! - **Compression** (`compress` feature, enabled by default): `save_splat_compressed` and
+//!   `write_splat_compressed` write gzip or zstd compressed SPLAT data.
+//! - **Preview** (`preview` feature): `render_preview` rasterizes a cheap CPU preview and
+//!   `compare_to_reference` diffs it against a stored PNG for headless visual regression tests.
+//! - **Transforms**: `Transform` moves, rotates and uniformly scales Gaussians before
+//!   quantization (`ply_to_splat_transformed` or `ConvertOptions::transform`);
+//!   `Transform::from_units` converts between meters, centimeters and millimeters, and
+//!   `AxisConvention` swaps Y/Z or flips handedness for viewers with other axes.
+//! - **Activations**: `ActivationMode::PreActivated` reads files that store linear scales and
+//!   opacities instead of log-scales and logits; `looks_pre_activated` detects likely cases.
+//! - **Sanitizing**: `SanitizeMode` drops Gaussians with NaN or infinite values, or replaces
+//!   them with transparent placeholders, instead of passing them on to renderers.
+//! - **Quantization**: `QuantizeMode` rounds colors and rotations to the nearest byte instead
+//!   of truncating, to match other converters.
+//! - **Rotation Order**: `RotationOrder` reads quaternions stored as `(x, y, z, w)` instead of
+//!   the usual `(w, x, y, z)`.
+//! - **Perceptual Importance**: `perceptual_importance` scores splats by coverage and local
+//!   color contrast, for `SortKey::Perceptual` ordering or `trim_by_perceptual_importance`.
+//! - **Structure of Arrays**: `to_soa` and `from_soa` convert to and from a planar layout
+//!   with contiguous position, scale, color and rotation planes.
+//! - **Normals**: `PlyGaussian::normal` keeps `nx`, `ny` and `nz`; `ply_to_splat_with_normals`
+//!   carries them through conversion for a separate sidecar written by `save_normals`.
+//! - **Merging**: `merge_ply` combines scenes captured in sections before a single global sort.
+//! - **Voxel Downsampling**: `downsample_voxel` keeps the most opaque Gaussian per voxel.
+//! - **Subsampling**: `subsample_to` keeps a reproducible random subset of a given size.
+//! - **Inspection**: `inspect_ply` reports elements, vertex count and missing properties from
+//!   the header alone.
+//! - **Debug Dumps**: `write_splats_csv` and `write_splats_json` write per-splat values in
+//!   human-readable form; `save_splats_as` picks the format from an `OutputFormat`.
+//! - **Statistics**: `compute_stats` summarizes bounds, scales and opacities of a scene.
+//! - **Opacity Bands**: `save_opacity_bands` splits a scene into files from most opaque to most
+//!   transparent, with uniform or quantile band boundaries (see `BandBoundaries`).
+//!
+//! ## Determinism
+//!
+//! The whole pipeline produces byte-identical output for the same input and options,
+//! independent of the number of threads and of thread scheduling:
+//!
+//! - Every parallel pass is an order-preserving map/filter/collect, so the surviving splats
+//!   keep their original relative order no matter which thread processed them.
+//! - Sorting uses a stable sort with a total order (`total_cmp`) on the key and a position
+//!   tie-break, so equal keys resolve the same way on every run and platform.
+//! - Passes that drop or merge splats must be expressed in terms of original indices and
+//!   never depend on hash map iteration order.
 
 use anyhow::{Context, Result};
 use bytemuck::{Pod, Zeroable};
 use ply_rs::parser::Parser;
-use ply_rs::ply::{Property, PropertyAccess};
+use ply_rs::ply::{
+    DefaultElement, ElementDef, Encoding, Header, Property, PropertyAccess, PropertyType,
+    ScalarType,
+};
 #[cfg(feature = "parallel")]
 use rayon::prelude::*;
 use std::fs::File;
-use std::io::{BufReader, Cursor, Write};
+use std::io::{BufRead, BufReader, Cursor, Write};
 use std::path::Path;
 
+mod activation;
+mod axes;
+mod bands;
+mod builder;
 #[cfg(feature = "cli")]
 pub mod cli;
+mod compact;
+mod compare;
+#[cfg(feature = "compress")]
+mod compress;
+mod container;
+mod dump;
+mod error;
+mod export;
+mod extended;
+mod filter;
+mod frames;
+mod inspect;
+mod lut;
+mod mask;
+mod merge;
+#[cfg(feature = "mmap")]
+mod mmap;
+mod morton;
+mod normals;
+mod perceptual;
+#[cfg(feature = "preview")]
+mod preview;
+mod quantize;
+mod rotation;
+mod sanitize;
+mod schema;
+mod selective;
+mod sh;
+mod soa;
+mod stats;
+mod streaming;
+mod subsample;
+mod tiles;
+mod transform;
+mod units;
+mod validate;
+mod voxel;
+mod vram;
+
+pub use activation::{ActivationMode, looks_pre_activated};
+pub use axes::AxisConvention;
+pub use bands::{
+    BandBoundaries, OpacityBand, band_path, bands_manifest_json, save_opacity_bands,
+    split_opacity_bands,
+};
+pub use builder::SplatBuilder;
+pub use compact::{SplatFormat, splats_from_bytes_format, splats_to_bytes_format};
+pub use compare::compare_scenes;
+#[cfg(feature = "compress")]
+pub use compress::{Compression, save_splat_compressed, write_splat_compressed};
+pub use container::{
+    SPLAT_CONTAINER_VERSION, SPLAT_HEADER_SIZE, SPLAT_MAGIC, SplatContainer, read_splat_headered,
+    save_splat_headered, write_splat_headered,
+};
+pub use dump::{
+    CSV_HEADER, OutputFormat, save_splats_as, write_splats_as, write_splats_csv, write_splats_json,
+};
+pub use error::{LoadWarning, Ply2SplatError, WarningHandler};
+pub use export::{
+    save_ply, splat_to_ply, splat_to_ply_with_mode, write_ply_ascii, write_ply_binary,
+};
+pub use extended::{
+    EXTENDED_MAGIC, EXTENDED_VERSION, ExtendedSplats, FLAG_CONFIDENCE, ply_to_splat_extended,
+    read_extended_splats, save_splat_extended, write_extended_splats,
+};
+pub use filter::{SplatFilter, ply_to_splat_filtered};
+pub use frames::{
+    FRAME_HEADER_SIZE, SplatFrameWriter, SplatFrames, decode_frame, encode_frame, splat_frames,
+};
+pub use inspect::{PlyInfo, inspect_ply, inspect_ply_bytes};
+pub use lut::ColorLut;
+pub use mask::MaskMode;
+pub use merge::merge_ply;
+#[cfg(feature = "mmap")]
+pub use mmap::{load_ply_mmap, load_ply_mmap_with_options};
+pub use morton::morton_codes;
+pub use normals::{ply_to_splat_with_normals, save_normals, write_normals};
+pub use perceptual::{perceptual_importance, trim_by_perceptual_importance};
+#[cfg(feature = "preview")]
+pub use preview::{PreviewView, compare_images, compare_to_reference, render_preview};
+pub use quantize::QuantizeMode;
+pub use rotation::RotationOrder;
+pub use sanitize::{SanitizeMode, is_finite_gaussian};
+pub use schema::{PlySchema, save_ply_with_schema, write_ply_with_schema};
+pub use selective::{load_ply_from_bytes_selective, load_ply_from_bytes_selective_with_options};
+pub use sh::ShMode;
+pub use soa::{SOA_HEADER_SIZE, SOA_MAGIC, SOA_VERSION, from_soa, to_soa};
+pub use stats::{OPACITY_BUCKETS, SplatStats, compute_stats};
+pub use streaming::{
+    StreamedCounts, convert_ply_to_splat_streaming, convert_ply_to_splat_streaming_with_options,
+};
+pub use subsample::subsample_to;
+pub use tiles::{GeoTile, geotile, save_geotiles, tiles_index_json};
+pub use transform::{Transform, ply_to_splat_transformed};
+pub use units::{LengthUnit, save_units_sidecar, units_sidecar_json};
+pub use validate::validate_gaussian_header;
+pub use voxel::downsample_voxel;
+pub use vram::{RendererProfile, estimate_vram};
 
 const SH_C0: f32 = 0.282_094_8;
 
+/// Names of the `vertex` properties read into `PlyGaussian`.
+const GAUSSIAN_PROPERTIES: [&str; 14] = [
+    "x", "y", "z", "f_dc_0", "f_dc_1", "f_dc_2", "opacity", "scale_0", "scale_1", "scale_2",
+    "rot_0", "rot_1", "rot_2", "rot_3",
+];
+
+/// Extracts a floating-point property value as `f32`.
+///
+/// Both `float` and `double` properties are accepted; `double` values are narrowed to `f32`.
+fn property_as_f32(property: &Property) -> Option<f32> {
+    match *property {
+        Property::Float(v) => Some(v),
+        Property::Double(v) => Some(v as f32),
+        _ => None,
+    }
+}
+
+/// Warns `on_warning` about recognized properties of the Gaussian element declared with a
+/// non-floating-point type.
+///
+/// Such properties cannot be interpreted and are left at their default of 0.
+fn warn_unsupported_property_types(header: &Header, element: &str, on_warning: &WarningHandler) {
+    let Some(vertex) = header.elements.get(element) else {
+        return;
+    };
+    for property in vertex.properties.values() {
+        let supported = matches!(
+            property.data_type,
+            PropertyType::Scalar(ScalarType::Float | ScalarType::Double)
+        );
+        if !supported && GAUSSIAN_PROPERTIES.contains(&property.name.as_str()) {
+            on_warning.warn(LoadWarning::UnsupportedPropertyType {
+                property: property.name.clone(),
+                data_type: format!("{:?}", property.data_type),
+            });
+        }
+    }
+}
+
 /// Represents a raw Gaussian Splat read from a PLY file.
 ///
 /// This struct holds the properties directly as they appear in standard Gaussian Splatting PLY files.
@@ -35,6 +247,13 @@ const SH_C0: f32 = 0.282_094_8;
 /// - `opacity`: Logit opacity (needs sigmoid)
 /// - `scale_*`: Log-scale (needs exp)
 /// - `rot_*`: Quaternion rotation (w, x, y, z order usually, but handled as raw floats here)
+/// - `f_rest`: Higher-order SH coefficients (`f_rest_*`), only populated when loading with
+///   [`LoadOptions::sh_rest`]
+/// - `confidence`: Optional per-Gaussian quality score (`confidence`), 0 when absent
+/// - `nx`, `ny`, `nz`: Optional normal written by some exporters, 0 when absent; see
+///   [`PlyGaussian::normal`]
+/// - `mask`: Whether the Gaussian is hidden, only populated when loading with
+///   [`LoadOptions::mask_property`]
 #[derive(Debug, Clone, Default)]
 pub struct PlyGaussian {
     pub x: f32,
@@ -51,6 +270,19 @@ pub struct PlyGaussian {
     pub rot_1: f32,
     pub rot_2: f32,
     pub rot_3: f32,
+    pub f_rest: Vec<f32>,
+    pub confidence: f32,
+    pub nx: f32,
+    pub ny: f32,
+    pub nz: f32,
+    pub mask: bool,
+}
+
+impl PlyGaussian {
+    /// The normal `(nx, ny, nz)`, all 0 when the PLY declares none.
+    pub fn normal(&self) -> [f32; 3] {
+        [self.nx, self.ny, self.nz]
+    }
 }
 
 impl PropertyAccess for PlyGaussian {
@@ -59,21 +291,28 @@ impl PropertyAccess for PlyGaussian {
     }
 
     fn set_property(&mut self, key: String, property: Property) {
-        match (key.as_str(), property) {
-            ("x", Property::Float(v)) => self.x = v,
-            ("y", Property::Float(v)) => self.y = v,
-            ("z", Property::Float(v)) => self.z = v,
-            ("f_dc_0", Property::Float(v)) => self.f_dc_0 = v,
-            ("f_dc_1", Property::Float(v)) => self.f_dc_1 = v,
-            ("f_dc_2", Property::Float(v)) => self.f_dc_2 = v,
-            ("opacity", Property::Float(v)) => self.opacity = v,
-            ("scale_0", Property::Float(v)) => self.scale_0 = v,
-            ("scale_1", Property::Float(v)) => self.scale_1 = v,
-            ("scale_2", Property::Float(v)) => self.scale_2 = v,
-            ("rot_0", Property::Float(v)) => self.rot_0 = v,
-            ("rot_1", Property::Float(v)) => self.rot_1 = v,
-            ("rot_2", Property::Float(v)) => self.rot_2 = v,
-            ("rot_3", Property::Float(v)) => self.rot_3 = v,
+        let Some(v) = property_as_f32(&property) else {
+            return; // Only floating-point properties are used
+        };
+        match key.as_str() {
+            "x" => self.x = v,
+            "y" => self.y = v,
+            "z" => self.z = v,
+            "f_dc_0" => self.f_dc_0 = v,
+            "f_dc_1" => self.f_dc_1 = v,
+            "f_dc_2" => self.f_dc_2 = v,
+            "opacity" => self.opacity = v,
+            "scale_0" => self.scale_0 = v,
+            "scale_1" => self.scale_1 = v,
+            "scale_2" => self.scale_2 = v,
+            "rot_0" => self.rot_0 = v,
+            "rot_1" => self.rot_1 = v,
+            "rot_2" => self.rot_2 = v,
+            "rot_3" => self.rot_3 = v,
+            "confidence" => self.confidence = v,
+            "nx" => self.nx = v,
+            "ny" => self.ny = v,
+            "nz" => self.nz = v,
             _ => {} // Ignore other properties
         }
     }
@@ -105,108 +344,406 @@ impl SplatPoint {
     /// 5. Packing everything into the compact 32-byte format.
     ///
     /// Returns a tuple of `(SplatPoint, sort_key)`, where `sort_key` is used for sorting splats
-    /// (usually by volume/opacity importance) to optimize rendering.
+    /// (by volume/opacity importance unless `ConvertOptions::sort_key` says otherwise) to
+    /// optimize rendering.
     pub fn from_ply(p: &PlyGaussian) -> (Self, f32) {
-        // Color
-        let r = ((0.5 + SH_C0 * p.f_dc_0).clamp(0.0, 1.0) * 255.0) as u8;
-        let g = ((0.5 + SH_C0 * p.f_dc_1).clamp(0.0, 1.0) * 255.0) as u8;
-        let b = ((0.5 + SH_C0 * p.f_dc_2).clamp(0.0, 1.0) * 255.0) as u8;
-
-        // Opacity (Sigmoid)
-        let opacity = (1.0 / (1.0 + (-p.opacity).exp())).clamp(0.0, 1.0);
-        let a = (opacity * 255.0) as u8;
+        Self::from_ply_with_options(p, &ConvertOptions::default())
+    }
 
-        // Scale (Exp)
-        let s0 = p.scale_0.exp();
-        let s1 = p.scale_1.exp();
-        let s2 = p.scale_2.exp();
+    /// Converts a raw `PlyGaussian` into a `SplatPoint` using the given options.
+    ///
+    /// See [`SplatPoint::from_ply`] for the individual steps. Only the color computation
+    /// depends on `options.sh_mode`; the 32-byte layout is the same in all modes. The
+    /// quaternion is read as `(w, x, y, z)`; `options.rotation_order` is applied by the
+    /// `ply_to_splat*` functions.
+    pub fn from_ply_with_options(p: &PlyGaussian, options: &ConvertOptions) -> (Self, f32) {
+        // Color
+        let [r, g, b] = match options.sh_mode {
+            ShMode::DcOnly => [p.f_dc_0, p.f_dc_1, p.f_dc_2].map(|f_dc| 0.5 + SH_C0 * f_dc),
+            ShMode::EvaluateAt(dir) => {
+                let dir = sh::normalize_dir(dir);
+                [0, 1, 2].map(|channel| 0.5 + sh::eval_sh(p, channel, dir))
+            }
+        }
+        .map(|c| options.quantize.unit(c));
+        let [r, g, b] = match &options.color_lut {
+            Some(lut) => [lut.map(0, r), lut.map(1, g), lut.map(2, b)],
+            None => [r, g, b],
+        };
 
-        // Rotation (Normalize -> Encode)
-        let q_len =
-            (p.rot_0 * p.rot_0 + p.rot_1 * p.rot_1 + p.rot_2 * p.rot_2 + p.rot_3 * p.rot_3).sqrt();
-        let (r0, r1, r2, r3) = if q_len > 0.0 {
-            (
-                p.rot_0 / q_len,
-                p.rot_1 / q_len,
-                p.rot_2 / q_len,
-                p.rot_3 / q_len,
-            )
+        // Opacity (Sigmoid unless pre-activated); masked Gaussians are fully transparent
+        let opacity = if p.mask {
+            0.0
         } else {
-            (1.0, 0.0, 0.0, 0.0)
+            options.activation.opacity(p)
         };
+        let a = options.quantize.unit(opacity);
+
+        // Scale (Exp unless pre-activated)
+        let scale = options.activation.scales(p);
 
-        let rot0 = (r0 * 128.0 + 128.0).clamp(0.0, 255.0) as u8;
-        let rot1 = (r1 * 128.0 + 128.0).clamp(0.0, 255.0) as u8;
-        let rot2 = (r2 * 128.0 + 128.0).clamp(0.0, 255.0) as u8;
-        let rot3 = (r3 * 128.0 + 128.0).clamp(0.0, 255.0) as u8;
+        // Rotation (Normalize -> Encode)
+        let rot = options
+            .quantize
+            .rotation([p.rot_0, p.rot_1, p.rot_2, p.rot_3]);
 
         let splat = SplatPoint {
             pos: [p.x, p.y, p.z],
-            scale: [s0, s1, s2],
+            scale,
             color: [r, g, b, a],
-            rot: [rot0, rot1, rot2, rot3],
+            rot,
         };
 
-        // Calculate sort key: -volume * alpha
-        // volume = exp(scale_sum)
-        let volume = (p.scale_0 + p.scale_1 + p.scale_2).exp();
-        let key = -(volume * opacity); // opacity is already calculated alpha
+        let key = match options.sort_key {
+            SortKey::Opacity => -opacity,
+            SortKey::DistanceFrom(camera) => {
+                let d = [0, 1, 2].map(|i| splat.pos[i] - camera[i]);
+                d[0] * d[0] + d[1] * d[1] + d[2] * d[2]
+            }
+            // Perceptual and Morton keys need the whole scene and replace these in
+            // `apply_sort_key`
+            SortKey::Importance | SortKey::Perceptual | SortKey::Morton | SortKey::None => {
+                // Calculate sort key: -volume * alpha
+                // volume = exp(scale_sum) for log-scales
+                let volume = options.activation.volume(p);
+                -(volume * opacity) // opacity is already calculated alpha
+            }
+        };
 
         (splat, key)
     }
 }
 
+/// Options controlling how PLY data is loaded.
+#[derive(Debug, Clone, Default)]
+pub struct LoadOptions {
+    /// Also capture the higher-order SH coefficients (`f_rest_*`) into `PlyGaussian::f_rest`.
+    ///
+    /// Disabled by default, since real captures carry up to 45 of them per vertex.
+    pub sh_rest: bool,
+    /// Name of a `vertex` property marking Gaussians to hide; any non-zero value sets
+    /// `PlyGaussian::mask`. Gaussians without the property stay unmasked.
+    pub mask_property: Option<String>,
+    /// Accept `vertex` elements that lack some of the expected Gaussian properties, leaving
+    /// them at 0 instead of failing with `Ply2SplatError::MissingProperties`.
+    pub allow_missing: bool,
+    /// Name of the element holding the Gaussians, for exporters that do not use `vertex`.
+    /// `None` reads `vertex`.
+    pub element: Option<String>,
+    /// Fail with `Ply2SplatError::CountMismatch` when the payload holds fewer Gaussians than
+    /// the header declares, instead of loading the ones present with a warning.
+    pub strict: bool,
+    /// Receives non-fatal problems such as a truncated payload; discarded by default.
+    pub on_warning: WarningHandler,
+}
+
+impl LoadOptions {
+    /// Name of the element the Gaussians are read from.
+    pub fn element_name(&self) -> &str {
+        self.element.as_deref().unwrap_or("vertex")
+    }
+}
+
+/// Error for a header lacking the element `name`, listing the elements it does have.
+fn missing_element(header: &Header, name: &str) -> Ply2SplatError {
+    Ply2SplatError::MissingElement {
+        name: name.to_string(),
+        available: header.elements.keys().cloned().collect(),
+    }
+}
+
+/// Parses a complete PLY stream and returns the records of its Gaussian element, `vertex`
+/// unless `options.element` names another one.
+///
+/// Unless `allow_missing` is set, the header is validated with `validate_gaussian_header`
+/// before any vertex is read. A payload ending early is reported according to
+/// `options.strict`; elements after the Gaussian one are not read.
+fn parse_vertices<E: PropertyAccess, R: BufRead>(
+    reader: &mut R,
+    source: &str,
+    options: &LoadOptions,
+) -> Result<Vec<E>, Ply2SplatError> {
+    let element = options.element_name();
+    let header = Parser::<E>::new()
+        .read_header(reader)
+        .map_err(|e| Ply2SplatError::Parse(format!("{source}: {e}")))?;
+    if !header.elements.contains_key(element) {
+        return Err(missing_element(&header, element));
+    }
+    if !options.allow_missing {
+        validate::validate_gaussian_element(&header, element)?;
+    }
+    warn_unsupported_property_types(&header, element, &options.on_warning);
+
+    let mut vertices = Vec::new();
+    for element_def in header.elements.values() {
+        if element_def.name != element {
+            // Elements before the Gaussian one have to be consumed to reach its records.
+            read_elements::<DefaultElement, _, Ply2SplatError>(
+                reader,
+                header.encoding,
+                element_def,
+                source,
+                |_| Ok(()),
+            )?;
+            continue;
+        }
+
+        // A header may overstate the count, so do not trust it for huge allocations.
+        vertices.reserve(element_def.count.min(MAX_PREALLOCATED_VERTICES));
+        let read = read_elements(reader, header.encoding, element_def, source, |v| {
+            vertices.push(v);
+            Ok::<_, Ply2SplatError>(())
+        })?;
+        validate::check_vertex_count(element_def.count, read, options.strict, &options.on_warning)?;
+        break;
+    }
+    Ok(vertices)
+}
+
+/// Upper bound of the vertices reserved up front from the header count.
+const MAX_PREALLOCATED_VERTICES: usize = 1 << 20;
+
+/// Reads up to `element_def.count` records of one element, passing each to `f`, and returns
+/// how many were read.
+///
+/// Input that ends before the declared count is not an error here; the caller compares the
+/// returned count against the header, see `validate::check_vertex_count`.
+pub(crate) fn read_elements<E, R, Err>(
+    reader: &mut R,
+    encoding: Encoding,
+    element_def: &ElementDef,
+    source: &str,
+    mut f: impl FnMut(E) -> Result<(), Err>,
+) -> Result<usize, Err>
+where
+    E: PropertyAccess,
+    R: BufRead,
+    Err: From<Ply2SplatError>,
+{
+    let parser = Parser::<E>::new();
+    let parse_error = |e: std::io::Error| {
+        Ply2SplatError::Parse(format!("{source}: '{}' element: {e}", element_def.name))
+    };
+    let mut line = String::new();
+    for read in 0..element_def.count {
+        let element = match encoding {
+            Encoding::Ascii => {
+                line.clear();
+                if reader.read_line(&mut line).map_err(parse_error)? == 0 {
+                    return Ok(read);
+                }
+                parser.read_ascii_element(&line, element_def)
+            }
+            Encoding::BinaryBigEndian => parser.read_big_endian_element(reader, element_def),
+            Encoding::BinaryLittleEndian => parser.read_little_endian_element(reader, element_def),
+        };
+        match element {
+            Ok(element) => f(element)?,
+            // A partial binary record at the end of the input
+            Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(read),
+            Err(e) => return Err(parse_error(e).into()),
+        }
+    }
+    Ok(element_def.count)
+}
+
+/// Parses a PLY stream into `PlyGaussian`s according to `options`.
+fn read_ply_gaussians<R: BufRead>(
+    reader: &mut R,
+    source: &str,
+    options: &LoadOptions,
+) -> Result<Vec<PlyGaussian>, Ply2SplatError> {
+    if let Some(mask_property) = &options.mask_property {
+        mask::read_masked_gaussians(reader, source, options, mask_property)
+    } else if options.sh_rest {
+        let vertices = parse_vertices::<sh::PlyGaussianWithRest, _>(reader, source, options)?;
+        Ok(vertices.into_iter().map(|v| v.0).collect())
+    } else {
+        parse_vertices(reader, source, options)
+    }
+}
+
 /// Loads PLY data from a byte slice and parses it into a vector of `PlyGaussian`.
 ///
 /// This function is useful for WASM environments where file I/O is not available,
-/// or when working with PLY data already in memory.
+/// or when working with PLY data already in memory. Fails with
+/// `Ply2SplatError::MissingProperties` if the `vertex` element lacks any expected property;
+/// see `LoadOptions::allow_missing`.
 ///
 /// # Arguments
 /// * `data` - A byte slice containing PLY file data.
 ///
 /// # Returns
 /// A `Result` containing the vector of parsed `PlyGaussian` structs or an error.
-pub fn load_ply_from_bytes(data: &[u8]) -> Result<Vec<PlyGaussian>> {
-    let mut cursor = Cursor::new(data);
-    let parser = Parser::<PlyGaussian>::new();
-    let ply = parser
-        .read_ply(&mut cursor)
-        .context("Failed to parse PLY data")?;
+pub fn load_ply_from_bytes(data: &[u8]) -> Result<Vec<PlyGaussian>, Ply2SplatError> {
+    load_ply_from_bytes_with_options(data, &LoadOptions::default())
+}
 
-    let vertices = ply
-        .payload
-        .get("vertex")
-        .context("PLY data has no 'vertex' element")?;
-    Ok(vertices.clone())
+/// Loads PLY data from a byte slice using the given `LoadOptions`.
+///
+/// # Arguments
+/// * `data` - A byte slice containing PLY file data.
+/// * `options` - Controls which optional properties are captured.
+///
+/// # Returns
+/// A `Result` containing the vector of parsed `PlyGaussian` structs or an error.
+pub fn load_ply_from_bytes_with_options(
+    data: &[u8],
+    options: &LoadOptions,
+) -> Result<Vec<PlyGaussian>, Ply2SplatError> {
+    let mut cursor = Cursor::new(data);
+    read_ply_gaussians(&mut cursor, "data", options)
 }
 
 /// Loads a PLY file and parses it into a vector of `PlyGaussian`.
 ///
-/// This function uses `ply-rs` to parse the file. It specifically looks for the "vertex" element.
+/// This function uses `ply-rs` to parse the file. It specifically looks for the "vertex" element
+/// and fails with `Ply2SplatError::MissingProperties` if it lacks any expected property.
+///
+/// # Arguments
+/// * `path` - Path to the .ply file.
+///
+/// # Returns
+/// A `Result` containing the vector of parsed `PlyGaussian` structs or an error.
+pub fn load_ply<P: AsRef<Path>>(path: P) -> Result<Vec<PlyGaussian>, Ply2SplatError> {
+    load_ply_with_options(path, &LoadOptions::default())
+}
+
+/// Loads a PLY file using the given `LoadOptions`.
 ///
 /// # Arguments
 /// * `path` - Path to the .ply file.
+/// * `options` - Controls which optional properties are captured.
 ///
 /// # Returns
 /// A `Result` containing the vector of parsed `PlyGaussian` structs or an error.
-pub fn load_ply<P: AsRef<Path>>(path: P) -> Result<Vec<PlyGaussian>> {
-    let f = File::open(path).context("Failed to open PLY file")?;
+pub fn load_ply_with_options<P: AsRef<Path>>(
+    path: P,
+    options: &LoadOptions,
+) -> Result<Vec<PlyGaussian>, Ply2SplatError> {
+    let f = File::open(path)?;
     let mut f = BufReader::with_capacity(10 * 1024 * 1024, f); // 10MB buffer
-    let parser = Parser::<PlyGaussian>::new();
-    let ply = parser
-        .read_ply(&mut f)
-        .context("Failed to parse PLY file")?;
+    read_ply_gaussians(&mut f, "file", options)
+}
+
+/// Options controlling the conversion from `PlyGaussian` to `SplatPoint`.
+///
+/// The default options reproduce `ply_to_splat(points, true)`.
+#[derive(Debug, Clone)]
+pub struct ConvertOptions {
+    /// Sort the splats by importance (volume * opacity).
+    pub sort: bool,
+    /// How the base color is derived from the spherical harmonics.
+    pub sh_mode: ShMode,
+    /// Tables remapping the quantized RGB bytes; `None` stores them unchanged.
+    pub color_lut: Option<ColorLut>,
+    /// Thresholds for dropping Gaussians; the default keeps all of them.
+    pub filter: SplatFilter,
+    /// Number of threads for the parallel stages; 0 uses the global rayon pool.
+    ///
+    /// Ignored without the `parallel` feature.
+    pub num_threads: usize,
+    /// Transform applied to every Gaussian that passes `filter`, before quantization.
+    pub transform: Transform,
+    /// What happens to Gaussians with `PlyGaussian::mask` set.
+    pub mask_mode: MaskMode,
+    /// Axis convention of the output, applied after `transform`.
+    pub axes: AxisConvention,
+    /// Key used when `sort` is enabled.
+    pub sort_key: SortKey,
+    /// Order of the quaternion components in the input, normalized before `transform`.
+    pub rotation_order: RotationOrder,
+    /// Handling of Gaussians with non-finite values, applied before `filter`.
+    pub sanitize: SanitizeMode,
+    /// Rounding of colors and rotations to bytes.
+    pub quantize: QuantizeMode,
+    /// Whether scales and opacities are stored raw or already activated.
+    pub activation: ActivationMode,
+    /// Receives non-fatal load problems of the streaming conversion, which parses the PLY
+    /// itself; discarded by default. See `LoadOptions::on_warning`.
+    pub on_warning: WarningHandler,
+}
 
-    let vertices = ply
-        .payload
-        .get("vertex")
-        .context("PLY file has no 'vertex' element")?;
-    Ok(vertices.clone())
+impl Default for ConvertOptions {
+    fn default() -> Self {
+        Self {
+            sort: true,
+            sh_mode: ShMode::DcOnly,
+            color_lut: None,
+            filter: SplatFilter::default(),
+            num_threads: 0,
+            transform: Transform::IDENTITY,
+            mask_mode: MaskMode::Transparent,
+            axes: AxisConvention::Unchanged,
+            sort_key: SortKey::Importance,
+            rotation_order: RotationOrder::Wxyz,
+            sanitize: SanitizeMode::Keep,
+            quantize: QuantizeMode::Truncate,
+            activation: ActivationMode::Raw,
+            on_warning: WarningHandler::default(),
+        }
+    }
+}
+
+/// Key by which splats are ordered when sorting is enabled.
+///
+/// Splats with equal keys are ordered by position, so every key gives deterministic output.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum SortKey {
+    /// Volume times opacity, largest first.
+    #[default]
+    Importance,
+    /// `perceptual_importance`, highest first. Needs a neighborhood pass over all splats.
+    Perceptual,
+    /// Opacity alone, most opaque first.
+    Opacity,
+    /// Distance to a camera position, nearest first, for front-to-back rendering.
+    ///
+    /// Measured in output coordinates, i.e. after `transform` and `axes`.
+    DistanceFrom([f32; 3]),
+    /// `morton_codes` of the centers, so spatially close splats end up next to each other.
+    /// Needs the bounding box of all splats.
+    Morton,
+    /// Keep the input order, as with `sort == false`.
+    None,
+}
+
+/// Replaces the per-splat importance keys when `sort_key` needs the whole scene.
+fn apply_sort_key<T>(data: &mut [((SplatPoint, f32), T)], sort_key: SortKey) {
+    match sort_key {
+        SortKey::Importance | SortKey::Opacity | SortKey::DistanceFrom(_) | SortKey::None => {}
+        SortKey::Perceptual => {
+            let splats: Vec<SplatPoint> = data.iter().map(|((s, _), _)| *s).collect();
+            for (((_, key), _), score) in data.iter_mut().zip(perceptual_importance(&splats)) {
+                *key = -score;
+            }
+        }
+        SortKey::Morton => {
+            let splats: Vec<SplatPoint> = data.iter().map(|((s, _), _)| *s).collect();
+            for (((_, key), _), code) in data.iter_mut().zip(morton_codes(&splats)) {
+                // Codes use 30 bits, more than an f32 holds exactly. As bit patterns they are
+                // non-negative floats, which `total_cmp` orders like the integers.
+                *key = f32::from_bits(code);
+            }
+        }
+    }
+}
+
+/// Orders keyed splats by key, then by position (x, y, z).
+///
+/// The position tie-break ensures deterministic output even across different platforms/architectures.
+fn compare_keyed(a: &(SplatPoint, f32), b: &(SplatPoint, f32)) -> std::cmp::Ordering {
+    a.1.total_cmp(&b.1)
+        .then_with(|| a.0.pos[0].total_cmp(&b.0.pos[0]))
+        .then_with(|| a.0.pos[1].total_cmp(&b.0.pos[1]))
+        .then_with(|| a.0.pos[2].total_cmp(&b.0.pos[2]))
 }
 
 /// Converts a list of `PlyGaussian` structs into the optimized `SplatPoint` format.
 ///
-/// This function performs the conversion in parallel using `rayon` (when the `parallel` feature is enabled).
+/// This function performs the conversion in parallel using `rayon` (when the `parallel` feature is enabled),
+/// and falls back to a single-threaded version otherwise.
 /// It optionally sorts the splats based on a calculated key (volume * opacity) to optimize rendering order.
 ///
 /// # Arguments
@@ -215,61 +752,277 @@ pub fn load_ply<P: AsRef<Path>>(path: P) -> Result<Vec<PlyGaussian>> {
 ///
 /// # Returns
 /// A vector of `SplatPoint` structs ready for saving/rendering.
-#[cfg(feature = "parallel")]
 pub fn ply_to_splat(ply_points: Vec<PlyGaussian>, sort: bool) -> Vec<SplatPoint> {
-    // Parallel convert to (SplatPoint, key)
-    let mut data: Vec<(SplatPoint, f32)> = ply_points
-        .into_par_iter()
-        .map(|p| SplatPoint::from_ply(&p))
-        .collect();
-
-    if sort {
-        // Parallel sort by key, tie-break by position (x, y, z)
-        // This ensures deterministic output even across different platforms/architectures
-        data.par_sort_by(|a, b| {
-            a.1.total_cmp(&b.1)
-                .then_with(|| a.0.pos[0].total_cmp(&b.0.pos[0]))
-                .then_with(|| a.0.pos[1].total_cmp(&b.0.pos[1]))
-                .then_with(|| a.0.pos[2].total_cmp(&b.0.pos[2]))
-        });
-    }
+    ply_to_splat_with_options(
+        ply_points,
+        &ConvertOptions {
+            sort,
+            ..Default::default()
+        },
+    )
+}
 
-    // Parallel strip key
-    data.into_par_iter().map(|(s, _)| s).collect()
+/// Converts a list of `PlyGaussian` structs into `SplatPoint`s using the given options.
+///
+/// # Arguments
+/// * `ply_points` - A vector of raw `PlyGaussian` data.
+/// * `options` - Conversion options (sorting, SH evaluation, ...).
+///
+/// # Returns
+/// A vector of `SplatPoint` structs ready for saving/rendering.
+pub fn ply_to_splat_with_options(
+    ply_points: Vec<PlyGaussian>,
+    options: &ConvertOptions,
+) -> Vec<SplatPoint> {
+    convert_with_progress(ply_points, options, None)
 }
 
-/// Converts a list of `PlyGaussian` structs into the optimized `SplatPoint` format.
+/// Converts a list of `PlyGaussian` structs, reporting progress of the convert phase.
 ///
-/// This is a single-threaded version for environments where rayon is not available.
-/// It optionally sorts the splats based on a calculated key (volume * opacity) to optimize rendering order.
+/// `progress(processed, total)` is called on the converting thread after every 65,536
+/// Gaussians, with `processed` counting Gaussians including those dropped by filters.
+/// The last call always reports `(total, total)`, also for an empty input; the sort phase
+/// that follows is not reported. The output is identical to `ply_to_splat`.
 ///
 /// # Arguments
 /// * `ply_points` - A vector of raw `PlyGaussian` data.
 /// * `sort` - If true, sorts the splats by importance (volume * opacity).
+/// * `progress` - Callback receiving `(processed, total)`.
+pub fn ply_to_splat_with_progress(
+    ply_points: Vec<PlyGaussian>,
+    sort: bool,
+    progress: impl Fn(usize, usize) + Sync,
+) -> Vec<SplatPoint> {
+    ply_to_splat_with_options_and_progress(
+        ply_points,
+        &ConvertOptions {
+            sort,
+            ..Default::default()
+        },
+        progress,
+    )
+}
+
+/// Converts a list of `PlyGaussian` structs using the given options, reporting progress.
 ///
-/// # Returns
-/// A vector of `SplatPoint` structs ready for saving/rendering.
-#[cfg(not(feature = "parallel"))]
-pub fn ply_to_splat(ply_points: Vec<PlyGaussian>, sort: bool) -> Vec<SplatPoint> {
-    // Single-threaded convert to (SplatPoint, key)
-    let mut data: Vec<(SplatPoint, f32)> = ply_points
-        .into_iter()
-        .map(|p| SplatPoint::from_ply(&p))
-        .collect();
-
-    if sort {
-        // Single-threaded sort by key, tie-break by position (x, y, z)
-        // This ensures deterministic output even across different platforms/architectures
-        data.sort_by(|a, b| {
-            a.1.total_cmp(&b.1)
-                .then_with(|| a.0.pos[0].total_cmp(&b.0.pos[0]))
-                .then_with(|| a.0.pos[1].total_cmp(&b.0.pos[1]))
-                .then_with(|| a.0.pos[2].total_cmp(&b.0.pos[2]))
-        });
-    }
+/// See `ply_to_splat_with_progress` for when `progress` is called.
+pub fn ply_to_splat_with_options_and_progress(
+    ply_points: Vec<PlyGaussian>,
+    options: &ConvertOptions,
+    progress: impl Fn(usize, usize) + Sync,
+) -> Vec<SplatPoint> {
+    convert_with_progress(ply_points, options, Some(&progress))
+}
+
+fn convert_with_progress(
+    ply_points: Vec<PlyGaussian>,
+    options: &ConvertOptions,
+    progress: Option<&(dyn Fn(usize, usize) + Sync)>,
+) -> Vec<SplatPoint> {
+    in_thread_pool(options.num_threads, || {
+        convert_in_current_pool(ply_points, options, progress)
+    })
+}
+
+/// Converts like `ply_to_splat_with_options` on the current rayon pool, ignoring
+/// `options.num_threads`.
+///
+/// For callers converting many batches inside one `ConversionPool`.
+pub(crate) fn convert_in_current_pool(
+    ply_points: Vec<PlyGaussian>,
+    options: &ConvertOptions,
+    progress: Option<&(dyn Fn(usize, usize) + Sync)>,
+) -> Vec<SplatPoint> {
+    let data = convert_keyed(ply_points, options, |_, _| (), progress);
 
     // Strip key
-    data.into_iter().map(|(s, _)| s).collect()
+    #[cfg(feature = "parallel")]
+    return data.into_par_iter().map(|((s, _), _)| s).collect();
+    #[cfg(not(feature = "parallel"))]
+    return data.into_iter().map(|((s, _), _)| s).collect();
+}
+
+/// Converts a list of `PlyGaussian` structs using at most `num_threads` threads.
+///
+/// The parallel map and sort run inside a dedicated rayon pool of `num_threads` threads
+/// instead of the global pool; `0` uses the global pool. The output is byte-identical
+/// for every thread count.
+///
+/// # Arguments
+/// * `ply_points` - A vector of raw `PlyGaussian` data.
+/// * `sort` - If true, sorts the splats by importance (volume * opacity).
+/// * `num_threads` - Number of worker threads, or 0 for the global pool.
+pub fn ply_to_splat_with_threads(
+    ply_points: Vec<PlyGaussian>,
+    sort: bool,
+    num_threads: usize,
+) -> Vec<SplatPoint> {
+    ply_to_splat_with_options(
+        ply_points,
+        &ConvertOptions {
+            sort,
+            num_threads,
+            ..Default::default()
+        },
+    )
+}
+
+/// Converts a list of `PlyGaussian` structs like `ply_to_splat_with_options`, also returning
+/// the original vertex index of every splat.
+///
+/// `permutation[i]` is the index in `ply_points` of the Gaussian that became `splats[i]`, so
+/// per-vertex data can follow the splats through sorting and filtering. The permutation is
+/// the identity exactly when conversion kept every Gaussian in file order.
+///
+/// # Panics
+/// Panics if `ply_points` holds more than `u32::MAX` Gaussians, as their indices would not
+/// fit the permutation.
+pub fn ply_to_splat_with_permutation(
+    ply_points: Vec<PlyGaussian>,
+    options: &ConvertOptions,
+) -> (Vec<SplatPoint>, Vec<u32>) {
+    assert!(
+        u32::try_from(ply_points.len()).is_ok(),
+        "ply_to_splat_with_permutation supports at most u32::MAX Gaussians"
+    );
+    in_thread_pool(options.num_threads, || {
+        convert_keyed(ply_points, options, |i, _| i as u32, None)
+            .into_iter()
+            .map(|((s, _), i)| (s, i))
+            .unzip()
+    })
+}
+
+/// Runs `f` inside a dedicated rayon pool of `num_threads` threads.
+///
+/// Runs `f` directly when `num_threads` is 0, when the pool cannot be created, or without
+/// the `parallel` feature.
+pub(crate) fn in_thread_pool<R: Send>(num_threads: usize, f: impl FnOnce() -> R + Send) -> R {
+    ConversionPool::new(num_threads).install(f)
+}
+
+/// A dedicated rayon pool of a fixed thread count, built once and entered any number of
+/// times.
+///
+/// Holds no pool when `num_threads` is 0, when the pool cannot be created, or without the
+/// `parallel` feature; `install` then runs on the calling thread's pool.
+pub(crate) struct ConversionPool {
+    #[cfg(feature = "parallel")]
+    pool: Option<rayon::ThreadPool>,
+}
+
+impl ConversionPool {
+    pub(crate) fn new(num_threads: usize) -> Self {
+        #[cfg(feature = "parallel")]
+        return Self {
+            pool: (num_threads > 0)
+                .then(|| {
+                    rayon::ThreadPoolBuilder::new()
+                        .num_threads(num_threads)
+                        .build()
+                        .ok()
+                })
+                .flatten(),
+        };
+        #[cfg(not(feature = "parallel"))]
+        {
+            let _ = num_threads;
+            Self {}
+        }
+    }
+
+    /// Runs `f` inside the pool.
+    pub(crate) fn install<R: Send>(&self, f: impl FnOnce() -> R + Send) -> R {
+        #[cfg(feature = "parallel")]
+        if let Some(pool) = &self.pool {
+            return pool.install(f);
+        }
+        f()
+    }
+}
+
+/// Number of Gaussians converted between two progress reports.
+const PROGRESS_CHUNK: usize = 64 * 1024;
+
+/// Splits `points` into owned chunks of at most `chunk_size`, in order.
+///
+/// Chunks are split off the end so that every Gaussian is moved only once.
+fn into_chunks(mut points: Vec<PlyGaussian>, chunk_size: usize) -> Vec<Vec<PlyGaussian>> {
+    let mut chunks = Vec::new();
+    while points.len() > chunk_size {
+        let at = points.len() - chunk_size;
+        chunks.push(points.split_off(at));
+    }
+    chunks.push(points);
+    chunks.reverse();
+    chunks
+}
+
+/// Runs the filter, convert and sort stages, carrying a per-Gaussian value produced by
+/// `extra` from the original index and the Gaussian along with each keyed splat.
+///
+/// With a `progress` callback the Gaussians are converted in chunks of `PROGRESS_CHUNK`,
+/// reporting `(processed, total)` after each one; without it they are converted in one pass.
+/// Input that is already in sort order, common when re-processing converted scenes, skips
+/// the sort after a linear check.
+fn convert_keyed<T: Send + Sync>(
+    ply_points: Vec<PlyGaussian>,
+    options: &ConvertOptions,
+    extra: impl Fn(usize, &PlyGaussian) -> T + Sync + Send,
+    progress: Option<&(dyn Fn(usize, usize) + Sync)>,
+) -> Vec<((SplatPoint, f32), T)> {
+    let total = ply_points.len();
+    let chunk_size = if progress.is_some() {
+        PROGRESS_CHUNK
+    } else {
+        total
+    };
+    let mut data: Vec<((SplatPoint, f32), T)> = Vec::with_capacity(total);
+    let mut processed = 0;
+    for chunk in into_chunks(ply_points, chunk_size) {
+        let offset = processed;
+        processed += chunk.len();
+
+        // Convert to ((SplatPoint, key), extra), in parallel when enabled
+        #[cfg(feature = "parallel")]
+        let chunk = chunk.into_par_iter();
+        #[cfg(not(feature = "parallel"))]
+        let chunk = chunk.into_iter();
+        let converted = chunk
+            .enumerate()
+            .filter_map(|(i, p)| Some((offset + i, options.sanitize.apply(p)?)))
+            .filter(|(_, p)| options.filter.keeps_as(p, options.activation))
+            .filter(|(_, p)| !(p.mask && options.mask_mode == MaskMode::Drop))
+            .map(|(i, p)| (i, options.rotation_order.to_wxyz(p)))
+            .map(|(i, p)| {
+                let p = options.transform.apply_as(p, options.activation);
+                (i, options.axes.apply(p))
+            })
+            .map(|(i, p)| (SplatPoint::from_ply_with_options(&p, options), extra(i, &p)));
+        #[cfg(feature = "parallel")]
+        data.par_extend(converted);
+        #[cfg(not(feature = "parallel"))]
+        data.extend(converted);
+
+        if let Some(progress) = progress {
+            progress(processed, total);
+        }
+    }
+
+    if options.sort && options.sort_key != SortKey::None {
+        apply_sort_key(&mut data, options.sort_key);
+        let in_order = |w: &[((SplatPoint, f32), T)]| compare_keyed(&w[0].0, &w[1].0).is_le();
+        // The sort is stable, so skipping it for sorted input gives the same output
+        #[cfg(feature = "parallel")]
+        if !data.par_windows(2).all(in_order) {
+            data.par_sort_by(|a, b| compare_keyed(&a.0, &b.0));
+        }
+        #[cfg(not(feature = "parallel"))]
+        if !data.windows(2).all(in_order) {
+            data.sort_by(|a, b| compare_keyed(&a.0, &b.0));
+        }
+    }
+    data
 }
 
 /// Saves a slice of `SplatPoint`s to a file in a raw binary format.
@@ -282,13 +1035,22 @@ pub fn ply_to_splat(ply_points: Vec<PlyGaussian>, sort: bool) -> Vec<SplatPoint>
 /// * `splats` - The data to write.
 pub fn save_splat<P: AsRef<Path>>(path: P, splats: &[SplatPoint]) -> Result<()> {
     let mut f = File::create(path).context("Failed to create output file")?;
+    write_splat(&mut f, splats)?;
+    f.flush()?;
+    Ok(())
+}
 
+/// Writes `SplatPoint`s to `writer` in the raw binary format of `save_splat`.
+///
+/// The writer is not flushed, so callers writing to a buffered handle such as a locked
+/// stdout should flush it afterwards.
+pub fn write_splat<W: Write>(writer: &mut W, splats: &[SplatPoint]) -> Result<()> {
     // Zero-copy write: Cast the slice of structs directly to a slice of bytes.
     // SplatPoint is #[repr(C)] and Pod, so this is safe and extremely fast.
     let bytes: &[u8] = bytemuck::cast_slice(splats);
-    f.write_all(bytes).context("Failed to write SPLAT data")?;
-
-    f.flush()?;
+    writer
+        .write_all(bytes)
+        .context("Failed to write SPLAT data")?;
     Ok(())
 }
 
@@ -307,10 +1069,85 @@ pub fn splats_to_bytes(splats: &[SplatPoint]) -> Vec<u8> {
     bytemuck::cast_slice(splats).to_vec()
 }
 
+/// Error returned when raw bytes cannot be interpreted as SPLAT data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SplatBytesError {
+    /// The length in bytes is not a multiple of 32.
+    InvalidLength(usize),
+    /// The length in bytes is not a multiple of 20, the `SplatFormat::Compact16` stride.
+    InvalidCompactLength(usize),
+    /// The data does not start at a 4-byte boundary, so it cannot be borrowed as splats.
+    Misaligned,
+}
+
+impl std::fmt::Display for SplatBytesError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidLength(len) => write!(
+                f,
+                "Invalid SPLAT data: size {len} is not a multiple of 32 bytes"
+            ),
+            Self::InvalidCompactLength(len) => write!(
+                f,
+                "Invalid compact SPLAT data: size {len} is not a multiple of 20 bytes"
+            ),
+            Self::Misaligned => write!(f, "SPLAT data is not aligned to 4 bytes"),
+        }
+    }
+}
+
+impl std::error::Error for SplatBytesError {}
+
+/// Returns the number of splats in raw SPLAT data, checking that its length is a multiple
+/// of 32 bytes.
+pub fn splat_count(data: &[u8]) -> Result<usize, SplatBytesError> {
+    if data.len().is_multiple_of(size_of::<SplatPoint>()) {
+        Ok(data.len() / size_of::<SplatPoint>())
+    } else {
+        Err(SplatBytesError::InvalidLength(data.len()))
+    }
+}
+
+/// Converts raw SPLAT data back into `SplatPoint`s, the inverse of `splats_to_bytes`.
+///
+/// The splats are copied, so `data` may have any alignment.
+pub fn splats_from_bytes(data: &[u8]) -> Result<Vec<SplatPoint>, SplatBytesError> {
+    splat_count(data)?;
+    Ok(data
+        .chunks_exact(size_of::<SplatPoint>())
+        .map(bytemuck::pod_read_unaligned)
+        .collect())
+}
+
+/// Borrows raw SPLAT data as `SplatPoint`s without copying.
+///
+/// Fails with `SplatBytesError::Misaligned` if `data` does not start at a 4-byte boundary;
+/// use `splats_from_bytes` for such buffers.
+pub fn splats_from_bytes_ref(data: &[u8]) -> Result<&[SplatPoint], SplatBytesError> {
+    if splat_count(data)? == 0 {
+        // Empty slices may have any dangling address
+        return Ok(&[]);
+    }
+    bytemuck::try_cast_slice(data).map_err(|_| SplatBytesError::Misaligned)
+}
+
+/// Converts PLY data bytes to splats in one call.
+///
+/// Equivalent to `load_ply_from_bytes` followed by `ply_to_splat`. The parsed Gaussians are
+/// moved into the conversion and dropped as soon as it is done, so callers never hold them
+/// next to the splats.
+///
+/// # Arguments
+/// * `ply_data` - A byte slice containing PLY file data.
+/// * `sort` - If true, sorts the splats by importance (volume * opacity).
+pub fn convert_bytes(ply_data: &[u8], sort: bool) -> Result<Vec<SplatPoint>, Ply2SplatError> {
+    Ok(ply_to_splat(load_ply_from_bytes(ply_data)?, sort))
+}
+
 /// Converts PLY data bytes to SPLAT format bytes.
 ///
-/// This is a convenience function that combines `load_ply_from_bytes`, `ply_to_splat`,
-/// and `splats_to_bytes` into a single call.
+/// This is a convenience function that combines `convert_bytes` and `splats_to_bytes` into
+/// a single call.
 ///
 /// # Arguments
 /// * `ply_data` - A byte slice containing PLY file data.
@@ -319,11 +1156,8 @@ pub fn splats_to_bytes(splats: &[SplatPoint]) -> Vec<u8> {
 /// # Returns
 /// A `Result` containing a tuple of (splat bytes, splat count) or an error.
 pub fn convert(ply_data: &[u8], sort: bool) -> Result<(Vec<u8>, usize)> {
-    let ply_points = load_ply_from_bytes(ply_data)?;
-    let count = ply_points.len();
-    let splats = ply_to_splat(ply_points, sort);
-    let bytes = splats_to_bytes(&splats);
-    Ok((bytes, count))
+    let splats = convert_bytes(ply_data, sort)?;
+    Ok((splats_to_bytes(&splats), splats.len()))
 }
 
 /// Converts a PLY file to a SPLAT file.
@@ -386,10 +1220,11 @@ mod tests {
 
     #[test]
     fn test_opacity_extremes() {
-        let mut p = PlyGaussian::default();
-
         // High opacity
-        p.opacity = 100.0;
+        let mut p = PlyGaussian {
+            opacity: 100.0,
+            ..Default::default()
+        };
         let splats = ply_to_splat(vec![p.clone()], false);
         assert_eq!(splats[0].color[3], 255);
 
@@ -423,6 +1258,271 @@ mod tests {
         assert_eq!(unsorted[1].pos[0], 0.0); // p2
     }
 
+    #[test]
+    fn test_sort_keys() {
+        let input: Vec<PlyGaussian> = [(4.0, 1.0), (-1.0, 3.0), (2.0, 2.0), (0.5, -2.0)]
+            .into_iter()
+            .map(|(x, opacity)| PlyGaussian {
+                x,
+                opacity,
+                ..Default::default()
+            })
+            .collect();
+        let order = |sort_key| {
+            let options = ConvertOptions {
+                sort_key,
+                ..Default::default()
+            };
+            ply_to_splat_with_options(input.clone(), &options)
+                .iter()
+                .map(|s| s.pos[0])
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            order(SortKey::DistanceFrom([1.0, 0.0, 0.0])),
+            [0.5, 2.0, -1.0, 4.0]
+        );
+        assert_eq!(order(SortKey::Opacity), [-1.0, 2.0, 4.0, 0.5]);
+        assert_eq!(order(SortKey::None), [4.0, -1.0, 2.0, 0.5]);
+    }
+
+    #[test]
+    fn test_permutation() {
+        let input: Vec<PlyGaussian> = [(4.0, 1.0), (-1.0, 3.0), (2.0, 2.0), (0.5, -2.0)]
+            .into_iter()
+            .map(|(x, opacity)| PlyGaussian {
+                x,
+                opacity,
+                ..Default::default()
+            })
+            .collect();
+        let unsorted = ply_to_splat(input.clone(), false);
+
+        let options = ConvertOptions::default();
+        let (splats, permutation) = ply_to_splat_with_permutation(input, &options);
+        assert_eq!(permutation, [1, 2, 0, 3]);
+        for (splat, &i) in splats.iter().zip(&permutation) {
+            assert_eq!(
+                bytemuck::bytes_of(splat),
+                bytemuck::bytes_of(&unsorted[i as usize])
+            );
+        }
+
+        // Input already in sort order is kept as is
+        let sorted: Vec<PlyGaussian> = [3.0, 2.0, 1.0, -2.0]
+            .into_iter()
+            .enumerate()
+            .map(|(i, opacity)| PlyGaussian {
+                x: i as f32,
+                opacity,
+                ..Default::default()
+            })
+            .collect();
+        let (_, permutation) = ply_to_splat_with_permutation(sorted, &options);
+        assert_eq!(permutation, [0, 1, 2, 3]);
+
+        // Filtered Gaussians are left out
+        let options = ConvertOptions {
+            filter: SplatFilter {
+                min_opacity: 0.4,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let (_, permutation) = ply_to_splat_with_permutation(
+            [0.0, -5.0, 5.0]
+                .map(|opacity| PlyGaussian {
+                    opacity,
+                    ..Default::default()
+                })
+                .to_vec(),
+            &options,
+        );
+        assert_eq!(permutation, [2, 0]);
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_output_independent_of_thread_count() {
+        // Many equal keys and duplicated positions exercise the tie-break and sort stability.
+        let points: Vec<PlyGaussian> = (0..10_000)
+            .map(|i| PlyGaussian {
+                x: (i % 7) as f32,
+                y: (i % 3) as f32,
+                opacity: (i % 5) as f32,
+                f_dc_0: i as f32 * 0.001,
+                ..Default::default()
+            })
+            .collect();
+
+        let run = |threads: usize| {
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .unwrap();
+            pool.install(|| splats_to_bytes(&ply_to_splat(points.clone(), true)))
+        };
+
+        let single = run(1);
+        assert_eq!(single, run(4));
+        assert_eq!(single, run(8));
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_culled_and_deduplicated_output_independent_of_thread_count() {
+        // Non-finite Gaussians, filtered ones and voxel duplicates all drop splats, which must
+        // not change the order of the survivors.
+        let points: Vec<PlyGaussian> = (0..20_000)
+            .map(|i| PlyGaussian {
+                x: if i % 97 == 0 {
+                    f32::NAN
+                } else {
+                    (i % 41) as f32 * 0.1
+                },
+                y: (i % 13) as f32 * 0.1,
+                z: if i % 89 == 0 { f32::INFINITY } else { 0.0 },
+                opacity: (i % 9) as f32 - 4.0,
+                scale_0: (i % 6) as f32 - 3.0,
+                f_dc_0: i as f32 * 0.001,
+                rot_0: 1.0,
+                ..Default::default()
+            })
+            .collect();
+
+        let run = |num_threads: usize| {
+            let options = ConvertOptions {
+                filter: SplatFilter {
+                    min_opacity: 0.1,
+                    max_scale: 5.0,
+                },
+                sanitize: SanitizeMode::Drop,
+                num_threads,
+                ..Default::default()
+            };
+            let deduplicated = downsample_voxel(points.clone(), 0.25);
+            splats_to_bytes(&ply_to_splat_with_options(deduplicated, &options))
+        };
+
+        let single = run(1);
+        assert!(!single.is_empty() && single.len() < points.len() * 32);
+        assert_eq!(single, run(4));
+        assert_eq!(single, run(8));
+    }
+
+    #[test]
+    fn test_progress_reports() {
+        let total = PROGRESS_CHUNK * 2 + 100;
+        let points: Vec<PlyGaussian> = (0..total)
+            .map(|i| PlyGaussian {
+                x: (i % 13) as f32,
+                opacity: (i % 5) as f32,
+                ..Default::default()
+            })
+            .collect();
+
+        let calls = std::sync::Mutex::new(Vec::new());
+        let splats = ply_to_splat_with_progress(points.clone(), true, |done, total| {
+            calls.lock().unwrap().push((done, total));
+        });
+        let calls = calls.into_inner().unwrap();
+        assert_eq!(calls.len(), 3);
+        assert!(calls.windows(2).all(|w| w[0].0 < w[1].0));
+        assert_eq!(calls.last(), Some(&(total, total)));
+        // Chunked conversion produces the same output
+        assert_eq!(
+            splats_to_bytes(&splats),
+            splats_to_bytes(&ply_to_splat(points, true))
+        );
+
+        let calls = std::sync::Mutex::new(Vec::new());
+        ply_to_splat_with_progress(Vec::new(), true, |done, total| {
+            calls.lock().unwrap().push((done, total));
+        });
+        assert_eq!(calls.into_inner().unwrap(), [(0, 0)]);
+    }
+
+    #[test]
+    fn test_with_threads_matches_default() {
+        let points: Vec<PlyGaussian> = (0..5_000)
+            .map(|i| PlyGaussian {
+                x: (i % 11) as f32,
+                opacity: (i % 4) as f32,
+                scale_0: (i % 3) as f32 * 0.1,
+                ..Default::default()
+            })
+            .collect();
+
+        let default = splats_to_bytes(&ply_to_splat(points.clone(), true));
+        let single = splats_to_bytes(&ply_to_splat_with_threads(points.clone(), true, 1));
+        assert_eq!(single, default);
+        let global = splats_to_bytes(&ply_to_splat_with_threads(points, true, 0));
+        assert_eq!(global, default);
+    }
+
+    #[test]
+    fn test_load_ply_double_properties() {
+        let ply_content = b"ply
+format ascii 1.0
+element vertex 2
+property double x
+property double y
+property double z
+property double f_dc_0
+property double f_dc_1
+property double f_dc_2
+property double opacity
+property double scale_0
+property double scale_1
+property double scale_2
+property double rot_0
+property double rot_1
+property double rot_2
+property double rot_3
+end_header
+1.5 -2.25 3.125 0.5 0.5 0.5 0.0 0.1 0.1 0.1 1.0 0.0 0.0 0.0
+-4.0 5.0 1e-3 0.1 0.2 0.3 2.0 0.2 0.3 0.4 0.0 1.0 0.0 0.0
+";
+
+        let gaussians = load_ply_from_bytes(ply_content).unwrap();
+        assert_eq!(gaussians.len(), 2);
+        assert_eq!(gaussians[0].x, 1.5);
+        assert_eq!(gaussians[0].y, -2.25);
+        assert_eq!(gaussians[0].z, 3.125);
+        assert_eq!(gaussians[1].x, -4.0);
+        assert_eq!(gaussians[1].y, 5.0);
+        assert_eq!(gaussians[1].z, 1e-3);
+        assert_eq!(gaussians[1].opacity, 2.0);
+        assert_eq!(gaussians[1].scale_2, 0.4);
+        assert_eq!(gaussians[1].rot_1, 1.0);
+    }
+
+    #[test]
+    fn test_unsupported_property_type_warning() {
+        let ply_content = b"ply
+format ascii 1.0
+element vertex 1
+property float x
+property int opacity
+end_header
+1.0 3
+";
+        let warnings = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = warnings.clone();
+        let options = LoadOptions {
+            allow_missing: true,
+            on_warning: WarningHandler::new(move |w| sink.lock().unwrap().push(w.to_string())),
+            ..Default::default()
+        };
+        let gaussians = load_ply_from_bytes_with_options(ply_content, &options).unwrap();
+        assert_eq!((gaussians[0].x, gaussians[0].opacity), (1.0, 0.0));
+        assert_eq!(
+            *warnings.lock().unwrap(),
+            ["PLY property 'opacity' has unsupported type Scalar(Int) and will be read as 0"]
+        );
+    }
+
     #[test]
     fn test_load_ply_from_bytes() {
         let ply_content = b"ply
@@ -456,6 +1556,47 @@ end_header
         assert_eq!(gaussians[0].z, 3.0);
     }
 
+    #[test]
+    fn test_splats_from_bytes() {
+        let splats: Vec<SplatPoint> = (0..3)
+            .map(|i| SplatPoint {
+                pos: [i as f32, 2.0, 3.0],
+                scale: [0.5; 3],
+                color: [i as u8, 1, 2, 3],
+                rot: [255, 128, 128, 128],
+            })
+            .collect();
+        let bytes = splats_to_bytes(&splats);
+
+        let owned = splats_from_bytes(&bytes).unwrap();
+        assert_eq!(splats_to_bytes(&owned), bytes);
+        let borrowed = splats_from_bytes_ref(&bytes).unwrap();
+        assert_eq!(splats_to_bytes(borrowed), bytes);
+        assert_eq!(splat_count(&bytes), Ok(3));
+
+        // Misaligned data can still be copied
+        let mut shifted = vec![0u8];
+        shifted.extend_from_slice(&bytes);
+        assert_eq!(
+            splats_to_bytes(&splats_from_bytes(&shifted[1..]).unwrap()),
+            bytes
+        );
+
+        assert!(splats_from_bytes(&[]).unwrap().is_empty());
+        assert!(splats_from_bytes_ref(&[]).unwrap().is_empty());
+
+        let err = splats_from_bytes(&[0; 33]).unwrap_err();
+        assert_eq!(err, SplatBytesError::InvalidLength(33));
+        assert_eq!(
+            err.to_string(),
+            "Invalid SPLAT data: size 33 is not a multiple of 32 bytes"
+        );
+        assert_eq!(
+            splats_from_bytes_ref(&[0; 33]).unwrap_err(),
+            SplatBytesError::InvalidLength(33)
+        );
+    }
+
     #[test]
     fn test_splats_to_bytes() {
         let splat = SplatPoint {
@@ -502,5 +1643,15 @@ end_header
         let (bytes, count) = convert(ply_content, true).expect("Failed to convert");
         assert_eq!(count, 2);
         assert_eq!(bytes.len(), 64); // 2 splats * 32 bytes
+
+        for sort in [true, false] {
+            let splats = convert_bytes(ply_content, sort).unwrap();
+            let expected = ply_to_splat(load_ply_from_bytes(ply_content).unwrap(), sort);
+            assert_eq!(splats_to_bytes(&splats), splats_to_bytes(&expected));
+        }
+        assert!(matches!(
+            convert_bytes(b"not a ply", true),
+            Err(Ply2SplatError::Parse(_))
+        ));
     }
 }
@@ -0,0 +1,73 @@
+//! Custom color quantization through lookup tables.
+//!
+//! A color channel in `[0, 1]` is quantized to a byte according to `QuantizeMode`. A
+//! `ColorLut` remaps that byte afterwards, e.g. to match the response curve of a specific
+//! display. Opacity is never remapped.
+
+use anyhow::{Result, bail};
+
+/// Lookup tables mapping each quantized color byte to the stored byte, one per RGB channel.
+///
+/// The color is quantized with `QuantizeMode::unit` first, so table entry `i` receives the
+/// colors around `i / 255` and every value in `[0, 1]` has an entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColorLut(pub [[u8; 256]; 3]);
+
+impl ColorLut {
+    /// Applies the same table to all three channels.
+    pub fn uniform(table: [u8; 256]) -> Self {
+        Self([table; 3])
+    }
+
+    /// Reads 256 bytes (one table for all channels) or 768 bytes (the red, green and blue
+    /// tables in turn).
+    pub fn from_bytes(data: &[u8]) -> Result<Self> {
+        let mut tables = [[0u8; 256]; 3];
+        match data.len() {
+            256 => tables.iter_mut().for_each(|t| t.copy_from_slice(data)),
+            768 => {
+                for (table, chunk) in tables.iter_mut().zip(data.chunks_exact(256)) {
+                    table.copy_from_slice(chunk);
+                }
+            }
+            len => bail!("Invalid color LUT: expected 256 or 768 entries, got {len}"),
+        }
+        Ok(Self(tables))
+    }
+
+    /// Maps the quantized `byte` of `channel` (0 = red, 1 = green, 2 = blue).
+    pub fn map(&self, channel: usize, byte: u8) -> u8 {
+        self.0[channel][byte as usize]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ConvertOptions, PlyGaussian, SplatPoint};
+
+    #[test]
+    fn test_color_lut() {
+        // Red saturates at 1.0, green and blue stay at 0.5
+        let p = PlyGaussian {
+            f_dc_0: 10.0,
+            rot_0: 1.0,
+            ..Default::default()
+        };
+        let inverting: [u8; 256] = std::array::from_fn(|i| 255 - i as u8);
+        let options = ConvertOptions {
+            color_lut: Some(ColorLut::uniform(inverting)),
+            ..Default::default()
+        };
+        let (splat, _) = SplatPoint::from_ply_with_options(&p, &options);
+        // Bright red becomes dark, 127 becomes 128, alpha is left alone
+        assert_eq!(splat.color, [0, 128, 128, 127]);
+
+        let per_channel = [[0u8; 256], [1; 256], [2; 256]].concat();
+        let lut = ColorLut::from_bytes(&per_channel).unwrap();
+        assert_eq!((lut.map(0, 9), lut.map(1, 9), lut.map(2, 9)), (0, 1, 2));
+        assert_eq!(ColorLut::from_bytes(&inverting).unwrap().map(1, 0), 255);
+        let err = ColorLut::from_bytes(&[0; 255]).unwrap_err();
+        assert!(err.to_string().contains("got 255"));
+    }
+}
@@ -0,0 +1,163 @@
+//! Hiding Gaussians tagged by a mask property in the PLY file.
+//!
+//! Editing tools can mark Gaussians with a boolean or integer `vertex` property. Loading with
+//! `LoadOptions::mask_property` sets `PlyGaussian::mask` for every Gaussian whose value is
+//! non-zero, and `ConvertOptions::mask_mode` decides what happens to them on conversion.
+
+use crate::{LoadOptions, LoadWarning, Ply2SplatError, PlyGaussian, parse_vertices, sh};
+use ply_rs::ply::{DefaultElement, Property, PropertyAccess};
+use std::io::BufRead;
+
+/// What happens to masked Gaussians during conversion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum MaskMode {
+    /// Keep masked splats but make them fully transparent (alpha 0).
+    #[default]
+    Transparent,
+    /// Drop masked splats from the output.
+    Drop,
+}
+
+/// Returns whether a mask property value marks its Gaussian; lists never do.
+fn is_set(property: &Property) -> bool {
+    match *property {
+        Property::Char(v) => v != 0,
+        Property::UChar(v) => v != 0,
+        Property::Short(v) => v != 0,
+        Property::UShort(v) => v != 0,
+        Property::Int(v) => v != 0,
+        Property::UInt(v) => v != 0,
+        Property::Float(v) => v != 0.0,
+        Property::Double(v) => v != 0.0,
+        _ => false,
+    }
+}
+
+/// Reads Gaussians like `read_ply_gaussians`, additionally capturing `mask_property`.
+///
+/// The property name is only known at runtime, so vertices are parsed generically first.
+pub(crate) fn read_masked_gaussians<R: BufRead>(
+    reader: &mut R,
+    source: &str,
+    options: &LoadOptions,
+    mask_property: &str,
+) -> Result<Vec<PlyGaussian>, Ply2SplatError> {
+    let vertices = parse_vertices::<DefaultElement, _>(reader, source, options)?;
+    if vertices
+        .first()
+        .is_some_and(|v| !v.contains_key(mask_property))
+    {
+        options
+            .on_warning
+            .warn(LoadWarning::MissingMaskProperty(mask_property.to_string()));
+    }
+
+    Ok(vertices
+        .into_iter()
+        .map(|element| {
+            let mut gaussian = sh::PlyGaussianWithRest::new();
+            for (key, property) in element {
+                if key == mask_property {
+                    gaussian.0.mask = is_set(&property);
+                } else if options.sh_rest || !key.starts_with("f_rest_") {
+                    gaussian.set_property(key, property);
+                }
+            }
+            gaussian.0
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        ConvertOptions, LoadOptions, LoadWarning, MaskMode, WarningHandler,
+        load_ply_from_bytes_with_options, ply_to_splat_with_options,
+    };
+
+    const PLY: &[u8] = b"ply
+format ascii 1.0
+element vertex 3
+property float x
+property float opacity
+property uchar mask
+end_header
+0.0 2.0 0
+1.0 2.0 1
+2.0 2.0 0
+";
+
+    fn load(mask_property: &str) -> Vec<crate::PlyGaussian> {
+        load_ply_from_bytes_with_options(
+            PLY,
+            &LoadOptions {
+                mask_property: Some(mask_property.to_string()),
+                allow_missing: true,
+                ..Default::default()
+            },
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_masked_gaussian_is_transparent() {
+        let points = load("mask");
+        assert_eq!(
+            points.iter().map(|p| p.mask).collect::<Vec<_>>(),
+            [false, true, false]
+        );
+
+        let splats = ply_to_splat_with_options(
+            points,
+            &ConvertOptions {
+                sort: false,
+                ..Default::default()
+            },
+        );
+        let alphas: Vec<u8> = splats.iter().map(|s| s.color[3]).collect();
+        assert_eq!(alphas[1], 0);
+        assert!(alphas[0] > 200 && alphas[2] > 200);
+        // Positions are untouched
+        assert_eq!(splats[1].pos[0], 1.0);
+    }
+
+    #[test]
+    fn test_masked_gaussian_is_dropped() {
+        let splats = ply_to_splat_with_options(
+            load("mask"),
+            &ConvertOptions {
+                mask_mode: MaskMode::Drop,
+                ..Default::default()
+            },
+        );
+        let mut xs: Vec<f32> = splats.iter().map(|s| s.pos[0]).collect();
+        xs.sort_by(f32::total_cmp);
+        assert_eq!(xs, [0.0, 2.0]);
+    }
+
+    #[test]
+    fn test_missing_mask_property() {
+        let warnings = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = warnings.clone();
+        let points = load_ply_from_bytes_with_options(
+            PLY,
+            &LoadOptions {
+                mask_property: Some("hidden".to_string()),
+                allow_missing: true,
+                on_warning: WarningHandler::new(move |w| sink.lock().unwrap().push(w.clone())),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert!(points.iter().all(|p| !p.mask));
+        assert_eq!(
+            *warnings.lock().unwrap(),
+            [LoadWarning::MissingMaskProperty("hidden".to_string())]
+        );
+        assert_eq!(
+            ply_to_splat_with_options(points, &ConvertOptions::default()).len(),
+            3
+        );
+    }
+}
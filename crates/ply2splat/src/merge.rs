@@ -0,0 +1,46 @@
+//! Combining scenes captured in sections into one.
+
+use crate::PlyGaussian;
+
+/// Concatenates the Gaussians of several files, in order.
+///
+/// Merge before conversion rather than concatenating converted outputs: the importance sort
+/// then runs once over all Gaussians, so the result is ordered globally instead of per file.
+pub fn merge_ply(files: &[Vec<PlyGaussian>]) -> Vec<PlyGaussian> {
+    files.concat()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{load_ply_from_bytes, ply_to_splat};
+
+    fn ply(vertices: [(f32, f32); 2]) -> Vec<u8> {
+        let mut ply = String::from(
+            "ply\nformat ascii 1.0\nelement vertex 2\nproperty float x\nproperty float y\n\
+             property float z\nproperty float f_dc_0\nproperty float f_dc_1\n\
+             property float f_dc_2\nproperty float opacity\nproperty float scale_0\n\
+             property float scale_1\nproperty float scale_2\nproperty float rot_0\n\
+             property float rot_1\nproperty float rot_2\nproperty float rot_3\nend_header\n",
+        );
+        for (x, scale) in vertices {
+            ply += &format!("{x} 0 0 0 0 0 5 {scale} {scale} {scale} 1 0 0 0\n");
+        }
+        ply.into_bytes()
+    }
+
+    #[test]
+    fn test_merge_sorts_globally() {
+        let a = load_ply_from_bytes(&ply([(0.0, -3.0), (1.0, 0.0)])).unwrap();
+        let b = load_ply_from_bytes(&ply([(2.0, -1.0), (3.0, 1.0)])).unwrap();
+        let merged = merge_ply(&[a, b]);
+        assert_eq!(merged.len(), 4);
+
+        // Largest first, interleaving the two sources
+        let xs: Vec<f32> = ply_to_splat(merged, true)
+            .iter()
+            .map(|s| s.pos[0])
+            .collect();
+        assert_eq!(xs, [3.0, 1.0, 2.0, 0.0]);
+    }
+}
@@ -0,0 +1,88 @@
+//! Memory-mapped loading of large PLY files (`mmap` feature).
+//!
+//! Mapping the file lets the selective binary reader decode vertices straight from the page
+//! cache, instead of copying multi-gigabyte files through a `BufReader` first.
+
+use crate::{LoadOptions, Ply2SplatError, PlyGaussian, load_ply_from_bytes_selective_with_options};
+use memmap2::Mmap;
+use std::fs::File;
+use std::path::Path;
+
+/// Loads a PLY file by memory-mapping it.
+///
+/// Binary PLYs whose `vertex` records have a fixed size are decoded directly from the mapping
+/// by byte offset, like `load_ply_from_bytes_selective`; ASCII files and other layouts fall
+/// back to the `ply-rs` parser on the mapped bytes. The result is identical to `load_ply`.
+///
+/// # Safety assumptions
+/// Mapping a file is only sound while no one else modifies or truncates it: the mapping
+/// would change under the parser, and truncation raises `SIGBUS` on access. The file must
+/// therefore not be written to while this function runs. The mapping is dropped before
+/// returning, so the returned Gaussians never borrow from the file.
+///
+/// # Arguments
+/// * `path` - Path to the .ply file.
+///
+/// # Returns
+/// A `Result` containing the vector of parsed `PlyGaussian` structs or an error.
+pub fn load_ply_mmap<P: AsRef<Path>>(path: P) -> Result<Vec<PlyGaussian>, Ply2SplatError> {
+    load_ply_mmap_with_options(path, &LoadOptions::default())
+}
+
+/// Loads a PLY file by memory-mapping it, using the given `LoadOptions`.
+///
+/// See `load_ply_mmap` for the safety assumptions and
+/// `load_ply_from_bytes_selective_with_options` for how the options are honored.
+pub fn load_ply_mmap_with_options<P: AsRef<Path>>(
+    path: P,
+    options: &LoadOptions,
+) -> Result<Vec<PlyGaussian>, Ply2SplatError> {
+    let file = File::open(path)?;
+    // SAFETY: the file is opened read-only and, per the documented contract, not modified
+    // while mapped; the mapping does not outlive this function.
+    let map = unsafe { Mmap::map(&file)? };
+    load_ply_from_bytes_selective_with_options(&map, options)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::selective::tests::{assert_same, binary_ply, sample_vertices};
+    use crate::{LoadWarning, WarningHandler, load_ply};
+
+    #[test]
+    fn test_mmap_matches_load_ply() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("scene.ply");
+        std::fs::write(&path, binary_ply(&sample_vertices(100), 2)).unwrap();
+
+        assert_same(&load_ply_mmap(&path).unwrap(), &load_ply(&path).unwrap());
+    }
+
+    #[test]
+    fn test_mmap_passes_warnings() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("scene.ply");
+        let mut data = binary_ply(&sample_vertices(3), 0);
+        data.truncate(data.len() - 10);
+        std::fs::write(&path, data).unwrap();
+
+        let warnings = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = warnings.clone();
+        let options = LoadOptions {
+            on_warning: WarningHandler::new(move |w| sink.lock().unwrap().push(w.clone())),
+            ..Default::default()
+        };
+        assert_eq!(
+            load_ply_mmap_with_options(&path, &options).unwrap().len(),
+            2
+        );
+        assert_eq!(
+            *warnings.lock().unwrap(),
+            [LoadWarning::CountMismatch {
+                declared: 3,
+                actual: 2
+            }]
+        );
+    }
+}
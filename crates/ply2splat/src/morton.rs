@@ -0,0 +1,95 @@
+//! Morton (Z-order) codes of splat centers, for spatially coherent ordering.
+//!
+//! Every center is quantized to a 1024³ grid spanning the bounding box of all finite
+//! centers, and the bits of the three cell indices are interleaved. Sorting by the code keeps
+//! splats of the same grid region contiguous, which tiled renderers read with fewer cache
+//! misses than the scattered importance order.
+
+use crate::SplatPoint;
+
+/// Bits per axis of the grid.
+const BITS: u32 = 10;
+
+/// Spreads the low 10 bits of `v` so that two zero bits follow each of them.
+fn spread_bits(v: u32) -> u32 {
+    let mut v = v & 0x3ff;
+    v = (v | (v << 16)) & 0x0300_00ff;
+    v = (v | (v << 8)) & 0x0300_f00f;
+    v = (v | (v << 4)) & 0x030c_30c3;
+    v = (v | (v << 2)) & 0x0924_9249;
+    v
+}
+
+/// Returns the 30-bit Morton code of every splat center, with x in the lowest bit.
+///
+/// Non-finite coordinates fall into cell 0 of their axis.
+pub fn morton_codes(splats: &[SplatPoint]) -> Vec<u32> {
+    let mut min = [f32::INFINITY; 3];
+    let mut max = [f32::NEG_INFINITY; 3];
+    for s in splats {
+        for axis in 0..3 {
+            if s.pos[axis].is_finite() {
+                min[axis] = min[axis].min(s.pos[axis]);
+                max[axis] = max[axis].max(s.pos[axis]);
+            }
+        }
+    }
+
+    let cells = ((1 << BITS) - 1) as f32;
+    let scale: [f32; 3] = std::array::from_fn(|axis| {
+        let extent = max[axis] - min[axis];
+        if extent > 0.0 { cells / extent } else { 0.0 }
+    });
+    splats
+        .iter()
+        .map(|s| {
+            let cell = |axis: usize| {
+                // NaN casts to 0
+                ((s.pos[axis] - min[axis]) * scale[axis]).clamp(0.0, cells) as u32
+            };
+            spread_bits(cell(0)) | (spread_bits(cell(1)) << 1) | (spread_bits(cell(2)) << 2)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ConvertOptions, PlyGaussian, SortKey, ply_to_splat_with_options};
+
+    #[test]
+    fn test_spread_bits() {
+        assert_eq!(spread_bits(0b1011), 0b001_000_001_001);
+        assert_eq!(spread_bits(0x3ff), 0x0924_9249);
+    }
+
+    #[test]
+    fn test_morton_groups_neighbors() {
+        // A 4x4 grid in the z = 0 plane, listed row by row
+        let points: Vec<PlyGaussian> = (0..16)
+            .map(|i| PlyGaussian {
+                x: (i % 4) as f32,
+                y: (i / 4) as f32,
+                opacity: i as f32,
+                ..Default::default()
+            })
+            .collect();
+        let options = ConvertOptions {
+            sort_key: SortKey::Morton,
+            ..Default::default()
+        };
+        let order: Vec<(f32, f32)> = ply_to_splat_with_options(points, &options)
+            .iter()
+            .map(|s| (s.pos[0], s.pos[1]))
+            .collect();
+
+        // Every 2x2 quadrant is contiguous, visited in Z order
+        let quadrant = |(x, y): (f32, f32)| (x >= 2.0) as u8 + 2 * (y >= 2.0) as u8;
+        let quadrants: Vec<u8> = order.iter().map(|&p| quadrant(p)).collect();
+        assert_eq!(quadrants, [0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3]);
+        assert_eq!(
+            &order[..4],
+            [(0.0, 0.0), (1.0, 0.0), (0.0, 1.0), (1.0, 1.0)]
+        );
+    }
+}
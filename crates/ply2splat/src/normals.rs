@@ -0,0 +1,109 @@
+//! Per-splat normals for relighting tools.
+//!
+//! Some exporters write `nx`, `ny` and `nz` per vertex. The 32-byte `SplatPoint` has no room
+//! for them, so they travel next to the splats and are written to a separate sidecar: `n`
+//! little-endian `f32` triples, in the same order as the splats of the `.splat` file.
+
+use crate::{ConvertOptions, PlyGaussian, SplatPoint, convert_keyed, in_thread_pool};
+use anyhow::{Context, Result};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// Converts Gaussians like `ply_to_splat_with_options`, keeping each Gaussian's normal
+/// attached to its splat through filtering and sorting.
+///
+/// Normals are rotated by `options.transform` and remapped by `options.axes` like the
+/// orientations of the splats.
+pub fn ply_to_splat_with_normals(
+    ply_points: Vec<PlyGaussian>,
+    options: &ConvertOptions,
+) -> (Vec<SplatPoint>, Vec<[f32; 3]>) {
+    in_thread_pool(options.num_threads, || {
+        convert_keyed(ply_points, options, |_, p| p.normal(), None)
+            .into_iter()
+            .map(|((s, _), n)| (s, n))
+            .unzip()
+    })
+}
+
+/// Writes normals as the sidecar described in the module documentation.
+pub fn write_normals<W: Write>(writer: &mut W, normals: &[[f32; 3]]) -> Result<()> {
+    for n in normals.iter().flatten() {
+        writer.write_all(&n.to_le_bytes())?;
+    }
+    Ok(())
+}
+
+/// Saves normals to a sidecar file at `path`.
+pub fn save_normals<P: AsRef<Path>>(path: P, normals: &[[f32; 3]]) -> Result<()> {
+    let f = File::create(path).context("Failed to create normals file")?;
+    let mut writer = BufWriter::new(f);
+    write_normals(&mut writer, normals).context("Failed to write normals")?;
+    writer.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Transform, load_ply_from_bytes};
+
+    const HEADER: &str = "ply\nformat ascii 1.0\nelement vertex 2\nproperty float x\n\
+        property float y\nproperty float z\nproperty float f_dc_0\nproperty float f_dc_1\n\
+        property float f_dc_2\nproperty float opacity\nproperty float scale_0\n\
+        property float scale_1\nproperty float scale_2\nproperty float rot_0\n\
+        property float rot_1\nproperty float rot_2\nproperty float rot_3\n";
+
+    #[test]
+    fn test_normals_parsed() {
+        let ply = format!(
+            "{HEADER}property float nx\nproperty float ny\nproperty float nz\nend_header\n\
+             0 0 0 0 0 0 -5 0 0 0 1 0 0 0 0 0 1\n\
+             1 0 0 0 0 0 5 0 0 0 1 0 0 0 1 0 0\n"
+        );
+        let points = load_ply_from_bytes(ply.as_bytes()).unwrap();
+        assert_eq!(points[0].normal(), [0.0, 0.0, 1.0]);
+        assert_eq!(points[1].normal(), [1.0, 0.0, 0.0]);
+
+        // The opaque second Gaussian sorts first and takes its normal along
+        let (splats, normals) = ply_to_splat_with_normals(points, &ConvertOptions::default());
+        assert_eq!(splats[0].pos[0], 1.0);
+        assert_eq!(normals, [[1.0, 0.0, 0.0], [0.0, 0.0, 1.0]]);
+
+        let mut sidecar = Vec::new();
+        write_normals(&mut sidecar, &normals).unwrap();
+        assert_eq!(sidecar.len(), 24);
+        assert_eq!(sidecar[0..4], 1.0f32.to_le_bytes());
+    }
+
+    #[test]
+    fn test_normals_default_to_zero() {
+        let ply = format!(
+            "{HEADER}end_header\n0 0 0 0 0 0 0 0 0 0 1 0 0 0\n1 0 0 0 0 0 0 0 0 0 1 0 0 0\n"
+        );
+        let points = load_ply_from_bytes(ply.as_bytes()).unwrap();
+        assert!(points.iter().all(|p| p.normal() == [0.0; 3]));
+    }
+
+    #[test]
+    fn test_normals_rotate_with_transform() {
+        let p = PlyGaussian {
+            nx: 1.0,
+            ..Default::default()
+        };
+        // 90 degrees about z, with a scale and offset that must not affect the normal
+        let h = std::f32::consts::FRAC_1_SQRT_2;
+        let options = ConvertOptions {
+            transform: Transform {
+                translation: [5.0, 0.0, 0.0],
+                rotation: [h, 0.0, 0.0, h],
+                scale: 3.0,
+            },
+            ..Default::default()
+        };
+        let (_, normals) = ply_to_splat_with_normals(vec![p], &options);
+        let [x, y, z] = normals[0];
+        assert!(x.abs() < 1e-6 && (y - 1.0).abs() < 1e-6 && z.abs() < 1e-6);
+    }
+}
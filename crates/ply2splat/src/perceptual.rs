@@ -0,0 +1,217 @@
+//! Perceptual importance: how much visible detail a splat carries.
+//!
+//! The score of a splat is `coverage * (CONTRAST_FLOOR + contrast)`:
+//!
+//! - `coverage` is its alpha times the area of its largest cross-section (the product of its
+//!   two largest scales), a proxy for how much of the screen it covers.
+//! - `contrast` is the mean RGB distance (normalized to `[0, 1]`) between the splat and its
+//!   `NEIGHBORS` nearest neighbors, found through a uniform spatial grid.
+//!
+//! Splats in flat, uniformly colored regions therefore score lower than equally large and
+//! opaque splats on edges and texture, which makes the score a better basis for trimming
+//! than the plain volume * opacity importance.
+
+use crate::SplatPoint;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use std::collections::HashMap;
+
+/// Number of nearest neighbors compared against each splat.
+const NEIGHBORS: usize = 8;
+/// Upper bound on the candidates examined per splat, keeping dense cells affordable.
+const MAX_CANDIDATES: usize = 256;
+/// Average number of splats per grid cell.
+const SPLATS_PER_CELL: f32 = 8.0;
+/// Share of the score kept by splats without any contrast.
+const CONTRAST_FLOOR: f32 = 0.1;
+
+type Cell = (i64, i64, i64);
+
+/// Uniform grid over splat centers.
+struct SpatialGrid {
+    cell_size: f32,
+    cells: HashMap<Cell, Vec<usize>>,
+}
+
+impl SpatialGrid {
+    fn new(splats: &[SplatPoint]) -> Self {
+        let mut min = [f32::INFINITY; 3];
+        let mut max = [f32::NEG_INFINITY; 3];
+        for s in splats {
+            for i in 0..3 {
+                min[i] = min[i].min(s.pos[i]);
+                max[i] = max[i].max(s.pos[i]);
+            }
+        }
+        // Flat scenes would have zero volume, so give every axis a minimal extent
+        let largest = (0..3).map(|i| max[i] - min[i]).fold(0.0, f32::max);
+        let volume: f32 = (0..3)
+            .map(|i| (max[i] - min[i]).max(largest * 1e-3).max(1e-6))
+            .product();
+        let cell_size = (volume * SPLATS_PER_CELL / splats.len().max(1) as f32).cbrt();
+
+        let mut grid = Self {
+            cell_size,
+            cells: HashMap::new(),
+        };
+        for (i, s) in splats.iter().enumerate() {
+            grid.cells.entry(grid.cell(s.pos)).or_default().push(i);
+        }
+        grid
+    }
+
+    fn cell(&self, pos: [f32; 3]) -> Cell {
+        let [x, y, z] = pos.map(|c| (c / self.cell_size).floor() as i64);
+        (x, y, z)
+    }
+
+    /// Indices of up to `NEIGHBORS` nearest splats to `splats[index]` in the surrounding cells.
+    ///
+    /// Candidates are visited in a fixed cell and index order, so the result is deterministic.
+    fn neighbors(&self, splats: &[SplatPoint], index: usize) -> Vec<usize> {
+        let pos = splats[index].pos;
+        let (cx, cy, cz) = self.cell(pos);
+        let mut candidates: Vec<(f32, usize)> = Vec::new();
+        'cells: for dx in -1..=1 {
+            for dy in -1..=1 {
+                for dz in -1..=1 {
+                    let Some(cell) = self.cells.get(&(cx + dx, cy + dy, cz + dz)) else {
+                        continue;
+                    };
+                    for &j in cell {
+                        if j == index {
+                            continue;
+                        }
+                        if candidates.len() == MAX_CANDIDATES {
+                            break 'cells;
+                        }
+                        let d = splats[j].pos;
+                        let dist2 = (0..3).map(|k| (d[k] - pos[k]).powi(2)).sum::<f32>();
+                        candidates.push((dist2, j));
+                    }
+                }
+            }
+        }
+        candidates.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+        candidates
+            .into_iter()
+            .take(NEIGHBORS)
+            .map(|(_, j)| j)
+            .collect()
+    }
+}
+
+fn coverage(s: &SplatPoint) -> f32 {
+    let mut scale = s.scale;
+    scale.sort_by(f32::total_cmp);
+    s.color[3] as f32 / 255.0 * scale[1] * scale[2]
+}
+
+fn color_distance(a: &SplatPoint, b: &SplatPoint) -> f32 {
+    let squared: f32 = (0..3)
+        .map(|c| ((a.color[c] as f32 - b.color[c] as f32) / 255.0).powi(2))
+        .sum();
+    (squared / 3.0).sqrt()
+}
+
+/// Computes the perceptual importance of every splat, see the module documentation.
+///
+/// # Returns
+/// One non-negative score per splat, in input order; higher means more important.
+pub fn perceptual_importance(splats: &[SplatPoint]) -> Vec<f32> {
+    let grid = SpatialGrid::new(splats);
+    let score = |i: usize| {
+        let neighbors = grid.neighbors(splats, i);
+        let contrast = if neighbors.is_empty() {
+            0.0
+        } else {
+            neighbors
+                .iter()
+                .map(|&j| color_distance(&splats[i], &splats[j]))
+                .sum::<f32>()
+                / neighbors.len() as f32
+        };
+        coverage(&splats[i]) * (CONTRAST_FLOOR + contrast)
+    };
+
+    #[cfg(feature = "parallel")]
+    return (0..splats.len()).into_par_iter().map(score).collect();
+    #[cfg(not(feature = "parallel"))]
+    return (0..splats.len()).map(score).collect();
+}
+
+/// Keeps the `keep` perceptually most important splats, in their original order.
+///
+/// Ties are resolved in favor of the earlier splat, so the result is deterministic.
+pub fn trim_by_perceptual_importance(splats: &[SplatPoint], keep: usize) -> Vec<SplatPoint> {
+    if keep >= splats.len() {
+        return splats.to_vec();
+    }
+    let scores = perceptual_importance(splats);
+    let mut ranked: Vec<usize> = (0..splats.len()).collect();
+    ranked.sort_by(|&a, &b| scores[b].total_cmp(&scores[a]).then(a.cmp(&b)));
+    ranked.truncate(keep);
+    ranked.sort_unstable();
+    ranked.into_iter().map(|i| splats[i]).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn splat(pos: [f32; 3], color: [u8; 3]) -> SplatPoint {
+        SplatPoint {
+            pos,
+            scale: [0.1; 3],
+            color: [color[0], color[1], color[2], 200],
+            rot: [255, 128, 128, 128],
+        }
+    }
+
+    /// A gray 5x5x5 block with one red splat on its surface.
+    fn scene() -> Vec<SplatPoint> {
+        let mut splats = Vec::new();
+        for x in 0..5 {
+            for y in 0..5 {
+                for z in 0..5 {
+                    let color = if (x, y, z) == (4, 2, 2) {
+                        [255, 0, 0]
+                    } else {
+                        [128, 128, 128]
+                    };
+                    splats.push(splat([x as f32, y as f32, z as f32], color));
+                }
+            }
+        }
+        splats
+    }
+
+    fn index_of(splats: &[SplatPoint], pos: [f32; 3]) -> usize {
+        splats.iter().position(|s| s.pos == pos).unwrap()
+    }
+
+    #[test]
+    fn test_contrast_beats_bland() {
+        let splats = scene();
+        let scores = perceptual_importance(&splats);
+        let red = scores[index_of(&splats, [4.0, 2.0, 2.0])];
+        let interior = scores[index_of(&splats, [1.0, 1.0, 1.0])];
+        // Same size and opacity, but the red splat carries detail
+        assert!(red > interior * 2.0, "{red} vs {interior}");
+        // Its gray neighbors pick up some contrast as well
+        let next_to_red = scores[index_of(&splats, [3.0, 2.0, 2.0])];
+        assert!(next_to_red > interior);
+    }
+
+    #[test]
+    fn test_trim_keeps_detail() {
+        let splats = scene();
+        let kept = trim_by_perceptual_importance(&splats, 10);
+        assert_eq!(kept.len(), 10);
+        assert!(kept.iter().any(|s| s.color[0] == 255));
+        assert_eq!(
+            trim_by_perceptual_importance(&splats, 1000).len(),
+            splats.len()
+        );
+    }
+}
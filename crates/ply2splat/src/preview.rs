@@ -0,0 +1,233 @@
+//! Cheap CPU preview rasterization and comparison against reference renders.
+//!
+//! The rasterizer approximates every splat by an isotropic screen-space Gaussian sized by its
+//! largest scale axis and alpha-blends them back to front. It is meant for thumbnails and
+//! headless visual-regression checks, not as a faithful renderer.
+
+use crate::SplatPoint;
+use anyhow::{Context, Result, bail};
+use image::{Rgba, RgbaImage};
+use std::path::Path;
+
+/// Pinhole camera used for preview rendering.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PreviewView {
+    /// Image width in pixels.
+    pub width: u32,
+    /// Image height in pixels.
+    pub height: u32,
+    /// Camera position.
+    pub eye: [f32; 3],
+    /// Point the camera looks at.
+    pub target: [f32; 3],
+    /// Up direction of the camera.
+    pub up: [f32; 3],
+    /// Vertical field of view in degrees.
+    pub fov_y: f32,
+}
+
+impl PreviewView {
+    /// Creates a view looking from `eye` at `target` with +Y up and a 60° field of view.
+    pub fn new(width: u32, height: u32, eye: [f32; 3], target: [f32; 3]) -> Self {
+        Self {
+            width,
+            height,
+            eye,
+            target,
+            up: [0.0, 1.0, 0.0],
+            fov_y: 60.0,
+        }
+    }
+}
+
+fn sub(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn dot(a: [f32; 3], b: [f32; 3]) -> f32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn cross(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn normalize(v: [f32; 3]) -> [f32; 3] {
+    let len = dot(v, v).sqrt();
+    if len > 0.0 { v.map(|c| c / len) } else { v }
+}
+
+/// Renders a preview of `splats` from `view` on a black background.
+pub fn render_preview(splats: &[SplatPoint], view: &PreviewView) -> RgbaImage {
+    let (width, height) = (view.width as usize, view.height as usize);
+    let forward = normalize(sub(view.target, view.eye));
+    let right = normalize(cross(forward, view.up));
+    let up = cross(right, forward);
+    let focal = height as f32 * 0.5 / (view.fov_y.to_radians() * 0.5).tan();
+
+    // Project to (x, y, depth, sigma) in pixels
+    let mut projected: Vec<(f32, f32, f32, f32, &SplatPoint)> = splats
+        .iter()
+        .filter_map(|s| {
+            let d = sub(s.pos, view.eye);
+            let z = dot(d, forward);
+            if z <= 1e-3 {
+                return None;
+            }
+            let px = width as f32 * 0.5 + focal * dot(d, right) / z;
+            let py = height as f32 * 0.5 - focal * dot(d, up) / z;
+            let largest = s.scale[0].max(s.scale[1]).max(s.scale[2]);
+            let sigma = (focal * largest / z).max(0.5);
+            Some((px, py, z, sigma, s))
+        })
+        .collect();
+    // Back to front; stable with a total order for deterministic output
+    projected.sort_by(|a, b| b.2.total_cmp(&a.2));
+
+    let mut buffer = vec![[0.0f32; 3]; width * height];
+    for (px, py, _, sigma, s) in projected {
+        let radius = 3.0 * sigma;
+        let x0 = (px - radius).floor().max(0.0) as usize;
+        let y0 = (py - radius).floor().max(0.0) as usize;
+        let x1 = ((px + radius).ceil().max(0.0) as usize).min(width);
+        let y1 = ((py + radius).ceil().max(0.0) as usize).min(height);
+        let color = [s.color[0], s.color[1], s.color[2]].map(|c| c as f32 / 255.0);
+        let alpha = s.color[3] as f32 / 255.0;
+        let inv_two_sigma2 = 0.5 / (sigma * sigma);
+
+        for y in y0..y1 {
+            for x in x0..x1 {
+                let dx = x as f32 + 0.5 - px;
+                let dy = y as f32 + 0.5 - py;
+                let w = alpha * (-(dx * dx + dy * dy) * inv_two_sigma2).exp();
+                let pixel = &mut buffer[y * width + x];
+                for c in 0..3 {
+                    pixel[c] += (color[c] - pixel[c]) * w;
+                }
+            }
+        }
+    }
+
+    RgbaImage::from_fn(view.width, view.height, |x, y| {
+        let [r, g, b] = buffer[y as usize * width + x as usize]
+            .map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8);
+        Rgba([r, g, b, 255])
+    })
+}
+
+/// Compares two images of equal size.
+///
+/// # Returns
+/// A difference image holding the absolute per-channel RGB difference, and the mean squared
+/// error over all RGB channels normalized to `[0, 1]` (0 for identical images).
+pub fn compare_images(rendered: &RgbaImage, reference: &RgbaImage) -> Result<(RgbaImage, f64)> {
+    if rendered.dimensions() != reference.dimensions() {
+        bail!(
+            "Resolution mismatch: rendered {:?}, reference {:?}",
+            rendered.dimensions(),
+            reference.dimensions()
+        );
+    }
+
+    let mut squared_error = 0.0f64;
+    let diff = RgbaImage::from_fn(rendered.width(), rendered.height(), |x, y| {
+        let a = rendered.get_pixel(x, y);
+        let b = reference.get_pixel(x, y);
+        let mut out = [0, 0, 0, 255];
+        for c in 0..3 {
+            let d = a[c].abs_diff(b[c]);
+            squared_error += (d as f64 / 255.0).powi(2);
+            out[c] = d;
+        }
+        Rgba(out)
+    });
+    let samples = (rendered.width() as f64 * rendered.height() as f64 * 3.0).max(1.0);
+    Ok((diff, squared_error / samples))
+}
+
+/// Renders `splats` from `view` and compares the result against a reference PNG.
+///
+/// # Arguments
+/// * `splats` - The converted scene.
+/// * `reference_png` - Path to the stored reference render.
+/// * `view` - Camera to render from; its resolution must match the reference.
+///
+/// # Returns
+/// The difference image and the mean squared error, see `compare_images`.
+pub fn compare_to_reference<P: AsRef<Path>>(
+    splats: &[SplatPoint],
+    reference_png: P,
+    view: &PreviewView,
+) -> Result<(RgbaImage, f64)> {
+    let reference = image::open(reference_png.as_ref())
+        .with_context(|| {
+            format!(
+                "Failed to load reference image {:?}",
+                reference_png.as_ref()
+            )
+        })?
+        .to_rgba8();
+    compare_images(&render_preview(splats, view), &reference)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{PlyGaussian, ply_to_splat};
+
+    fn scene() -> Vec<SplatPoint> {
+        let points = (0..20)
+            .map(|i| PlyGaussian {
+                x: (i % 5) as f32 - 2.0,
+                y: (i / 5) as f32 - 1.5,
+                f_dc_0: (i % 3) as f32 - 1.0,
+                f_dc_1: 0.5,
+                opacity: 2.0,
+                scale_0: -2.0,
+                scale_1: -2.0,
+                scale_2: -2.0,
+                rot_0: 1.0,
+                ..Default::default()
+            })
+            .collect();
+        ply_to_splat(points, true)
+    }
+
+    #[test]
+    fn test_self_comparison() {
+        let splats = scene();
+        let view = PreviewView::new(64, 48, [0.0, 0.0, 8.0], [0.0, 0.0, 0.0]);
+        let rendered = render_preview(&splats, &view);
+        // Something was actually drawn
+        assert!(rendered.pixels().any(|p| p[0] > 0 || p[1] > 0));
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("reference.png");
+        rendered.save(&path).unwrap();
+
+        let (diff, mse) = compare_to_reference(&splats, &path, &view).unwrap();
+        assert!(mse < 1e-12);
+        assert!(diff.pixels().all(|p| p[0] == 0 && p[1] == 0 && p[2] == 0));
+
+        // A different view produces a measurable difference
+        let moved = PreviewView::new(64, 48, [1.0, 0.0, 8.0], [1.0, 0.0, 0.0]);
+        let (_, mse) = compare_to_reference(&splats, &path, &moved).unwrap();
+        assert!(mse > 0.0);
+    }
+
+    #[test]
+    fn test_resolution_mismatch() {
+        let splats = scene();
+        let view = PreviewView::new(32, 32, [0.0, 0.0, 8.0], [0.0, 0.0, 0.0]);
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("reference.png");
+        render_preview(&splats, &view).save(&path).unwrap();
+
+        let larger = PreviewView::new(64, 32, [0.0, 0.0, 8.0], [0.0, 0.0, 0.0]);
+        assert!(compare_to_reference(&splats, &path, &larger).is_err());
+    }
+}
@@ -0,0 +1,88 @@
+//! Quantization of colors and rotations to bytes.
+//!
+//! `SplatPoint` stores RGBA colors as `[0, 1] -> [0, 255]` and quaternion components as
+//! `[-1, 1] -> [0, 255]`. Historically both truncate toward zero, which biases colors
+//! slightly dark compared to tools that round to nearest; `QuantizeMode::Round` matches
+//! those tools instead.
+
+/// How floats are mapped to the bytes of `SplatPoint::color` and `SplatPoint::rot`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum QuantizeMode {
+    /// Truncate toward zero, as in earlier releases.
+    #[default]
+    Truncate,
+    /// Round to the nearest byte, ties away from zero.
+    Round,
+}
+
+impl QuantizeMode {
+    fn byte(self, v: f32) -> u8 {
+        match self {
+            Self::Truncate => v as u8,
+            Self::Round => v.round() as u8,
+        }
+    }
+
+    /// Quantizes a color or opacity value, clamped to `[0, 1]`.
+    pub fn unit(self, v: f32) -> u8 {
+        self.byte(v.clamp(0.0, 1.0) * 255.0)
+    }
+
+    /// Quantizes a component of a normalized quaternion, mapping `[-1, 1]` to `[0, 255]`.
+    pub fn quaternion(self, c: f32) -> u8 {
+        self.byte((c * 128.0 + 128.0).clamp(0.0, 255.0))
+    }
+
+    /// Normalizes a `(w, x, y, z)` quaternion and quantizes its components; a zero
+    /// quaternion becomes the identity.
+    pub fn rotation(self, q: [f32; 4]) -> [u8; 4] {
+        let [w, x, y, z] = q;
+        let len = (w * w + x * x + y * y + z * z).sqrt();
+        let unit = if len > 0.0 {
+            q.map(|c| c / len)
+        } else {
+            [1.0, 0.0, 0.0, 0.0]
+        };
+        unit.map(|c| self.quaternion(c))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ConvertOptions, PlyGaussian, SplatPoint};
+
+    #[test]
+    fn test_truncate_is_default() {
+        // f_dc = 0 gives exactly 0.5, i.e. 127.5 before quantization
+        let p = PlyGaussian {
+            rot_0: 1.0,
+            rot_1: -0.5,
+            ..Default::default()
+        };
+        let (splat, _) = SplatPoint::from_ply(&p);
+        assert_eq!(splat.color, [127, 127, 127, 127]);
+        // (1, -0.5) / sqrt(1.25) = (0.894.., -0.447..) -> 242.49.., 70.75..
+        assert_eq!(splat.rot, [242, 70, 128, 128]);
+
+        let options = ConvertOptions {
+            quantize: QuantizeMode::Round,
+            ..Default::default()
+        };
+        let (splat, _) = SplatPoint::from_ply_with_options(&p, &options);
+        assert_eq!(splat.color, [128, 128, 128, 128]);
+        assert_eq!(splat.rot, [242, 71, 128, 128]);
+    }
+
+    #[test]
+    fn test_bounds() {
+        for mode in [QuantizeMode::Truncate, QuantizeMode::Round] {
+            assert_eq!(mode.unit(-0.2), 0);
+            assert_eq!(mode.unit(1.7), 255);
+            assert_eq!(mode.quaternion(-1.0), 0);
+            assert_eq!(mode.quaternion(1.0), 255);
+            assert_eq!(mode.quaternion(0.0), 128);
+        }
+    }
+}
@@ -0,0 +1,63 @@
+//! Quaternion component order of the source PLY.
+//!
+//! The reference Gaussian Splatting exporter stores `rot_0..rot_3` as `(w, x, y, z)`, while
+//! some tools write `(x, y, z, w)`. The output always stores `SplatPoint::rot` as
+//! `(w, x, y, z)`, so reading a file with the wrong order mis-rotates every splat.
+
+use crate::PlyGaussian;
+
+/// Order of the quaternion components in `rot_0..rot_3` of the source PLY.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum RotationOrder {
+    /// `rot_0` is `w`, followed by `x`, `y` and `z`, as written by the reference exporter.
+    #[default]
+    Wxyz,
+    /// `rot_0..rot_2` are `x`, `y` and `z`, and `rot_3` is `w`.
+    Xyzw,
+}
+
+impl RotationOrder {
+    /// Reorders the quaternion of `p` into the `(w, x, y, z)` order used everywhere else.
+    pub fn to_wxyz(self, mut p: PlyGaussian) -> PlyGaussian {
+        match self {
+            Self::Wxyz => {}
+            Self::Xyzw => {
+                (p.rot_0, p.rot_1, p.rot_2, p.rot_3) = (p.rot_3, p.rot_0, p.rot_1, p.rot_2)
+            }
+        }
+        p
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ConvertOptions, ply_to_splat_with_options};
+
+    fn convert(rot: [f32; 4], rotation_order: RotationOrder) -> [u8; 4] {
+        let p = PlyGaussian {
+            rot_0: rot[0],
+            rot_1: rot[1],
+            rot_2: rot[2],
+            rot_3: rot[3],
+            ..Default::default()
+        };
+        let options = ConvertOptions {
+            rotation_order,
+            ..Default::default()
+        };
+        ply_to_splat_with_options(vec![p], &options)[0].rot
+    }
+
+    #[test]
+    fn test_byte_slots() {
+        // Unit quaternion with w = 0.8, x = 0.6, y = 0, z = 0
+        let byte = |c: f32| (c * 128.0 + 128.0) as u8;
+        let expected = [byte(0.8), byte(0.6), 128, 128];
+        assert_eq!(convert([0.8, 0.6, 0.0, 0.0], RotationOrder::Wxyz), expected);
+        assert_eq!(convert([0.6, 0.0, 0.0, 0.8], RotationOrder::Xyzw), expected);
+        // The same PLY values read with the other order give a different rotation
+        assert_ne!(convert([0.8, 0.6, 0.0, 0.0], RotationOrder::Xyzw), expected);
+    }
+}
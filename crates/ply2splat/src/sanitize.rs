@@ -0,0 +1,113 @@
+//! Handling of Gaussians with non-finite values.
+//!
+//! A NaN or infinite position, scale, opacity or rotation component survives the activations
+//! and produces splats that break renderers. Such Gaussians come from broken exporters or
+//! diverged training runs, so they are usually few and safe to discard.
+//!
+//! With `SanitizeMode::Keep` they are converted as they are. Their sort keys may then be NaN;
+//! since the sort compares keys with `total_cmp`, a NaN key with the sign bit clear sorts
+//! after every number and one with the sign bit set before every number. The order is
+//! therefore still deterministic, but such splats end up at either end of the output.
+
+use crate::PlyGaussian;
+
+/// What happens to Gaussians with a non-finite position, scale, opacity or rotation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SanitizeMode {
+    /// Convert them unchanged, so the non-finite values reach the output.
+    #[default]
+    Keep,
+    /// Drop them from the output.
+    Drop,
+    /// Keep their slot but replace every non-finite value with 0 and make them fully
+    /// transparent, so the splat count matches `Keep`.
+    Replace,
+}
+
+/// Returns whether the position, scales, opacity and rotation of `p` are all finite.
+///
+/// Colors are not checked: a non-finite color channel quantizes to a valid byte.
+pub fn is_finite_gaussian(p: &PlyGaussian) -> bool {
+    [
+        p.x, p.y, p.z, p.scale_0, p.scale_1, p.scale_2, p.opacity, p.rot_0, p.rot_1, p.rot_2,
+        p.rot_3,
+    ]
+    .iter()
+    .all(|v| v.is_finite())
+}
+
+impl SanitizeMode {
+    /// Applies the mode to one Gaussian, returning `None` if it is dropped.
+    pub fn apply(self, mut p: PlyGaussian) -> Option<PlyGaussian> {
+        if self == Self::Keep || is_finite_gaussian(&p) {
+            return Some(p);
+        }
+        match self {
+            Self::Keep => Some(p),
+            Self::Drop => None,
+            Self::Replace => {
+                for v in [
+                    &mut p.x,
+                    &mut p.y,
+                    &mut p.z,
+                    &mut p.scale_0,
+                    &mut p.scale_1,
+                    &mut p.scale_2,
+                    &mut p.rot_0,
+                    &mut p.rot_1,
+                    &mut p.rot_2,
+                    &mut p.rot_3,
+                ] {
+                    if !v.is_finite() {
+                        *v = 0.0;
+                    }
+                }
+                // The lowest logit, which the sigmoid maps to alpha 0
+                p.opacity = f32::MIN;
+                Some(p)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ConvertOptions, ply_to_splat_with_options};
+
+    fn convert(sanitize: SanitizeMode) -> Vec<crate::SplatPoint> {
+        let points = vec![
+            PlyGaussian {
+                x: 1.0,
+                ..Default::default()
+            },
+            PlyGaussian {
+                x: f32::NAN,
+                scale_1: f32::INFINITY,
+                ..Default::default()
+            },
+        ];
+        let options = ConvertOptions {
+            sanitize,
+            ..Default::default()
+        };
+        ply_to_splat_with_options(points, &options)
+    }
+
+    #[test]
+    fn test_sanitize_modes() {
+        let dropped = convert(SanitizeMode::Drop);
+        assert_eq!(dropped.len(), 1);
+        assert_eq!(dropped[0].pos[0], 1.0);
+
+        let replaced = convert(SanitizeMode::Replace);
+        assert_eq!(replaced.len(), 2);
+        let invalid = replaced.iter().find(|s| s.pos[0] == 0.0).unwrap();
+        assert_eq!(invalid.scale, [1.0; 3]);
+        assert_eq!(invalid.color[3], 0);
+
+        let kept = convert(SanitizeMode::Keep);
+        assert_eq!(kept.len(), 2);
+        assert!(kept.iter().any(|s| s.pos[0].is_nan()));
+    }
+}
@@ -0,0 +1,249 @@
+//! Capture of the original PLY header so that `save_ply_with_schema` can reproduce it.
+//!
+//! The schema keeps the encoding, comments, elements and every `vertex` property with its
+//! name, type and position. It is stored as a header-only PLY text (everything up to and
+//! including `end_header`), which makes the sidecar file readable by any PLY tool.
+
+use crate::PlyGaussian;
+use anyhow::{Context, Result, bail};
+use ply_rs::parser::Parser;
+use ply_rs::ply::{DefaultElement, Header, Property, PropertyType, ScalarType};
+use ply_rs::writer::Writer;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Cursor, Write};
+use std::path::Path;
+
+/// The header layout of an original PLY file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlySchema {
+    header: Header,
+}
+
+impl PlySchema {
+    fn read<R: BufRead>(reader: &mut R) -> Result<Self> {
+        let header = Parser::<DefaultElement>::new()
+            .read_header(reader)
+            .context("Failed to parse PLY header")?;
+        if !header.elements.contains_key("vertex") {
+            bail!("PLY header has no 'vertex' element");
+        }
+        Ok(Self { header })
+    }
+
+    /// Captures the schema from PLY data. Only the header is read; the payload may be absent.
+    pub fn from_bytes(data: &[u8]) -> Result<Self> {
+        Self::read(&mut Cursor::new(data))
+    }
+
+    /// Captures the schema from a PLY file or a schema sidecar written by `save`.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let f = File::open(path).context("Failed to open PLY schema file")?;
+        Self::read(&mut BufReader::new(f))
+    }
+
+    /// Names of the `vertex` properties in their original order.
+    pub fn vertex_properties(&self) -> Vec<&str> {
+        self.header.elements["vertex"]
+            .properties
+            .keys()
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Number of `vertex` records declared by the header.
+    pub fn vertex_count(&self) -> usize {
+        self.header.elements["vertex"].count
+    }
+
+    /// Serializes the schema as a header-only PLY text.
+    pub fn to_header_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        Writer::<DefaultElement>::new()
+            .write_header(&mut out, &self.header)
+            .expect("writing to a Vec cannot fail");
+        out
+    }
+
+    /// Writes the schema sidecar to `path`.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        std::fs::write(path, self.to_header_bytes()).context("Failed to write PLY schema file")
+    }
+}
+
+/// Returns the value of the `vertex` property `name` stored in `g`, if it is tracked.
+fn gaussian_value(g: &PlyGaussian, name: &str) -> Option<f32> {
+    Some(match name {
+        "x" => g.x,
+        "y" => g.y,
+        "z" => g.z,
+        "f_dc_0" => g.f_dc_0,
+        "f_dc_1" => g.f_dc_1,
+        "f_dc_2" => g.f_dc_2,
+        "opacity" => g.opacity,
+        "scale_0" => g.scale_0,
+        "scale_1" => g.scale_1,
+        "scale_2" => g.scale_2,
+        "rot_0" => g.rot_0,
+        "rot_1" => g.rot_1,
+        "rot_2" => g.rot_2,
+        "rot_3" => g.rot_3,
+        "confidence" => g.confidence,
+        "nx" => g.nx,
+        "ny" => g.ny,
+        "nz" => g.nz,
+        _ => {
+            let i = name.strip_prefix("f_rest_")?.parse::<usize>().ok()?;
+            *g.f_rest.get(i)?
+        }
+    })
+}
+
+/// Converts `value` to a property of the given type. Lists are written empty.
+fn typed_property(data_type: &PropertyType, value: f32) -> Property {
+    match data_type {
+        PropertyType::Scalar(scalar) => match scalar {
+            ScalarType::Char => Property::Char(value as i8),
+            ScalarType::UChar => Property::UChar(value as u8),
+            ScalarType::Short => Property::Short(value as i16),
+            ScalarType::UShort => Property::UShort(value as u16),
+            ScalarType::Int => Property::Int(value as i32),
+            ScalarType::UInt => Property::UInt(value as u32),
+            ScalarType::Float => Property::Float(value),
+            ScalarType::Double => Property::Double(value as f64),
+        },
+        PropertyType::List(_, scalar) => match scalar {
+            ScalarType::Char => Property::ListChar(Vec::new()),
+            ScalarType::UChar => Property::ListUChar(Vec::new()),
+            ScalarType::Short => Property::ListShort(Vec::new()),
+            ScalarType::UShort => Property::ListUShort(Vec::new()),
+            ScalarType::Int => Property::ListInt(Vec::new()),
+            ScalarType::UInt => Property::ListUInt(Vec::new()),
+            ScalarType::Float => Property::ListFloat(Vec::new()),
+            ScalarType::Double => Property::ListDouble(Vec::new()),
+        },
+    }
+}
+
+/// Writes Gaussians as a PLY whose header matches `schema`.
+///
+/// The encoding, comments and the names, types and order of the `vertex` properties are taken
+/// from the schema. Properties not tracked by `PlyGaussian` (and `f_rest_*` coefficients that
+/// were not loaded) are written as 0, list properties as empty lists. Other elements are kept
+/// in the header with a count of 0 since their data is not preserved.
+///
+/// # Arguments
+/// * `writer` - Destination for the PLY data.
+/// * `gaussians` - The Gaussians to write.
+/// * `schema` - The captured original header.
+pub fn write_ply_with_schema<W: Write>(
+    writer: &mut W,
+    gaussians: &[PlyGaussian],
+    schema: &PlySchema,
+) -> Result<()> {
+    let mut header = schema.header.clone();
+    for (name, element) in header.elements.iter_mut() {
+        element.count = if name == "vertex" { gaussians.len() } else { 0 };
+    }
+    let vertex_def = &header.elements["vertex"];
+
+    let ply_writer = Writer::<DefaultElement>::new();
+    ply_writer.write_header(writer, &header)?;
+    for g in gaussians {
+        let mut element = DefaultElement::new();
+        for (name, property) in &vertex_def.properties {
+            let value = gaussian_value(g, name).unwrap_or(0.0);
+            element.insert(name.clone(), typed_property(&property.data_type, value));
+        }
+        ply_writer.write_payload_of_element(writer, &vec![element], vertex_def, &header)?;
+    }
+    Ok(())
+}
+
+/// Saves Gaussians to a PLY file whose header matches `schema`.
+///
+/// See `write_ply_with_schema` for how untracked properties are handled.
+pub fn save_ply_with_schema<P: AsRef<Path>>(
+    path: P,
+    gaussians: &[PlyGaussian],
+    schema: &PlySchema,
+) -> Result<()> {
+    let f = File::create(path).context("Failed to create output file")?;
+    let mut writer = BufWriter::new(f);
+    write_ply_with_schema(&mut writer, gaussians, schema).context("Failed to write PLY data")?;
+    writer.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{load_ply_from_bytes, ply_to_splat, splat_to_ply};
+
+    const UNUSUAL: &[u8] = b"ply
+format ascii 1.0
+comment exported by a custom trainer
+element vertex 2
+property float opacity
+property uchar label
+property float rot_3
+property float rot_2
+property float rot_1
+property float rot_0
+property double z
+property float y
+property float x
+property float scale_2
+property float scale_1
+property float scale_0
+property float f_dc_2
+property float f_dc_1
+property float f_dc_0
+end_header
+0.5 3 0 0 0 1 3.0 2.0 1.0 -1 -2 -3 0.1 0.2 0.3
+-1.0 7 0 0 0 1 -3.0 -2.0 -1.0 -4 -5 -6 0 0 0
+";
+
+    #[test]
+    fn test_schema_round_trip_keeps_order() {
+        let schema = PlySchema::from_bytes(UNUSUAL).unwrap();
+        let splats = ply_to_splat(load_ply_from_bytes(UNUSUAL).unwrap(), false);
+        let gaussians = splat_to_ply(&splats);
+
+        let mut out = Vec::new();
+        write_ply_with_schema(&mut out, &gaussians, &schema).unwrap();
+
+        let regenerated = PlySchema::from_bytes(&out).unwrap();
+        assert_eq!(
+            regenerated.vertex_properties(),
+            [
+                "opacity", "label", "rot_3", "rot_2", "rot_1", "rot_0", "z", "y", "x", "scale_2",
+                "scale_1", "scale_0", "f_dc_2", "f_dc_1", "f_dc_0"
+            ]
+        );
+        let vertex = &regenerated.header.elements["vertex"];
+        assert_eq!(vertex.count, 2);
+        assert_eq!(
+            vertex.properties["z"].data_type,
+            PropertyType::Scalar(ScalarType::Double)
+        );
+        assert_eq!(
+            regenerated.header.comments,
+            ["exported by a custom trainer"]
+        );
+
+        // Values survive the regenerated file (positions exactly).
+        let reloaded = load_ply_from_bytes(&out).unwrap();
+        assert_eq!(
+            [reloaded[0].x, reloaded[0].y, reloaded[0].z],
+            [1.0, 2.0, 3.0]
+        );
+        assert_eq!(reloaded[1].x, -1.0);
+    }
+
+    #[test]
+    fn test_schema_sidecar_round_trip() {
+        let schema = PlySchema::from_bytes(UNUSUAL).unwrap();
+        let parsed = PlySchema::from_bytes(&schema.to_header_bytes()).unwrap();
+        assert_eq!(parsed, schema);
+    }
+}
@@ -0,0 +1,508 @@
+//! Selective binary PLY reader.
+//!
+//! Real-world Gaussian Splatting PLYs carry many properties that the conversion never uses
+//! (normals, the 45 `f_rest_*` coefficients, custom attributes). `ply-rs` decodes every one of
+//! them into a `Property` and hands it to `set_property` with a freshly allocated key, only for
+//! it to be ignored. For binary files the layout of a vertex is fixed, so this reader computes
+//! the byte offset of each property we need once from the header and decodes just those,
+//! skipping everything else without materializing it.
+
+use crate::{
+    LoadOptions, Ply2SplatError, PlyGaussian, load_ply_from_bytes_with_options, missing_element,
+    validate::{check_vertex_count, validate_gaussian_element},
+    warn_unsupported_property_types,
+};
+use ply_rs::parser::Parser;
+use ply_rs::ply::{ElementDef, Encoding, Header, PropertyType, ScalarType};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use std::io::Cursor;
+
+/// Returns the slot in `PlyGaussian` that stores the property `name`, if it is used.
+fn field_accessor(name: &str) -> Option<fn(&mut PlyGaussian) -> &mut f32> {
+    Some(match name {
+        "x" => |p| &mut p.x,
+        "y" => |p| &mut p.y,
+        "z" => |p| &mut p.z,
+        "f_dc_0" => |p| &mut p.f_dc_0,
+        "f_dc_1" => |p| &mut p.f_dc_1,
+        "f_dc_2" => |p| &mut p.f_dc_2,
+        "opacity" => |p| &mut p.opacity,
+        "scale_0" => |p| &mut p.scale_0,
+        "scale_1" => |p| &mut p.scale_1,
+        "scale_2" => |p| &mut p.scale_2,
+        "rot_0" => |p| &mut p.rot_0,
+        "rot_1" => |p| &mut p.rot_1,
+        "rot_2" => |p| &mut p.rot_2,
+        "rot_3" => |p| &mut p.rot_3,
+        "confidence" => |p| &mut p.confidence,
+        "nx" => |p| &mut p.nx,
+        "ny" => |p| &mut p.ny,
+        "nz" => |p| &mut p.nz,
+        _ => return None,
+    })
+}
+
+/// Size in bytes of a binary-encoded scalar.
+fn scalar_size(scalar: &ScalarType) -> usize {
+    match scalar {
+        ScalarType::Char | ScalarType::UChar => 1,
+        ScalarType::Short | ScalarType::UShort => 2,
+        ScalarType::Int | ScalarType::UInt | ScalarType::Float => 4,
+        ScalarType::Double => 8,
+    }
+}
+
+/// Size in bytes of one record of `element`, or `None` if it contains list properties.
+fn element_stride(element: &ElementDef) -> Option<usize> {
+    element
+        .properties
+        .values()
+        .map(|p| match &p.data_type {
+            PropertyType::Scalar(scalar) => Some(scalar_size(scalar)),
+            PropertyType::List(..) => None,
+        })
+        .sum()
+}
+
+/// A single property to extract from each vertex record.
+struct Field {
+    offset: usize,
+    double: bool,
+    accessor: fn(&mut PlyGaussian) -> &mut f32,
+}
+
+/// Byte layout of the Gaussian element (usually `vertex`) in a binary PLY payload.
+pub(crate) struct VertexLayout {
+    /// Offset of the first vertex record relative to the start of the payload.
+    pub(crate) start: usize,
+    /// Size of one vertex record in bytes.
+    pub(crate) stride: usize,
+    /// Number of declared vertices.
+    pub(crate) count: usize,
+    big_endian: bool,
+    fields: Vec<Field>,
+}
+
+impl VertexLayout {
+    /// Computes the layout of the element `name` from a parsed header.
+    ///
+    /// Returns `None` for ASCII files and for payloads whose records are not fixed-size
+    /// (list properties in or before the element). Fails if the elements before it declare
+    /// more bytes than fit in memory.
+    pub(crate) fn from_header(header: &Header, name: &str) -> Result<Option<Self>, Ply2SplatError> {
+        let big_endian = match header.encoding {
+            Encoding::Ascii => return Ok(None),
+            Encoding::BinaryBigEndian => true,
+            Encoding::BinaryLittleEndian => false,
+        };
+
+        let mut start: usize = 0;
+        for element in header.elements.values() {
+            let Some(stride) = element_stride(element) else {
+                return Ok(None);
+            };
+            if element.name != name {
+                start = stride
+                    .checked_mul(element.count)
+                    .and_then(|size| start.checked_add(size))
+                    .ok_or_else(|| {
+                        Ply2SplatError::Parse(format!(
+                            "data: '{}' element declares more bytes than fit in memory",
+                            element.name
+                        ))
+                    })?;
+                continue;
+            }
+
+            let mut fields = Vec::new();
+            let mut offset = 0;
+            for property in element.properties.values() {
+                let PropertyType::Scalar(scalar) = &property.data_type else {
+                    return Ok(None);
+                };
+                // Only floating-point properties are used, matching `PlyGaussian::set_property`
+                let double = match scalar {
+                    ScalarType::Float => Some(false),
+                    ScalarType::Double => Some(true),
+                    _ => None,
+                };
+                if let (Some(double), Some(accessor)) = (double, field_accessor(&property.name)) {
+                    fields.push(Field {
+                        offset,
+                        double,
+                        accessor,
+                    });
+                }
+                offset += scalar_size(scalar);
+            }
+
+            return Ok(Some(Self {
+                start,
+                stride,
+                count: element.count,
+                big_endian,
+                fields,
+            }));
+        }
+        Ok(None)
+    }
+
+    /// Decodes a single vertex record of exactly `stride` bytes.
+    pub(crate) fn decode(&self, record: &[u8]) -> PlyGaussian {
+        let mut p = PlyGaussian::default();
+        for field in &self.fields {
+            let value = if field.double {
+                let bytes: [u8; 8] = record[field.offset..field.offset + 8].try_into().unwrap();
+                if self.big_endian {
+                    f64::from_be_bytes(bytes) as f32
+                } else {
+                    f64::from_le_bytes(bytes) as f32
+                }
+            } else {
+                let bytes: [u8; 4] = record[field.offset..field.offset + 4].try_into().unwrap();
+                if self.big_endian {
+                    f32::from_be_bytes(bytes)
+                } else {
+                    f32::from_le_bytes(bytes)
+                }
+            };
+            *(field.accessor)(&mut p) = value;
+        }
+        p
+    }
+
+    /// Decodes all vertices from the payload (the bytes following `end_header`).
+    ///
+    /// A truncated payload yields the complete records it holds, or fails with
+    /// `Ply2SplatError::CountMismatch` when `options.strict`, like the generic loader.
+    pub(crate) fn decode_all(
+        &self,
+        payload: &[u8],
+        options: &LoadOptions,
+    ) -> Result<Vec<PlyGaussian>, Ply2SplatError> {
+        if self.stride == 0 {
+            return Ok(vec![PlyGaussian::default(); self.count]);
+        }
+        // A truncated leading element leaves no vertex records at all
+        let records = payload.get(self.start..).unwrap_or_default();
+        let count = self.count.min(records.len() / self.stride);
+        check_vertex_count(self.count, count, options.strict, &options.on_warning)?;
+        let records = &records[..count * self.stride];
+
+        #[cfg(feature = "parallel")]
+        let vertices = records
+            .par_chunks_exact(self.stride)
+            .map(|r| self.decode(r))
+            .collect();
+        #[cfg(not(feature = "parallel"))]
+        let vertices = records
+            .chunks_exact(self.stride)
+            .map(|r| self.decode(r))
+            .collect();
+        Ok(vertices)
+    }
+}
+
+/// Loads PLY data from a byte slice, decoding only the properties used for conversion.
+///
+/// For binary PLYs the needed properties are read directly by byte offset, which avoids the
+/// per-property allocations of the generic parser and is considerably faster for files with
+/// many unused properties. ASCII files and binary files with list properties fall back to
+/// `load_ply_from_bytes`. The result is identical to `load_ply_from_bytes` in all cases.
+///
+/// # Arguments
+/// * `data` - A byte slice containing PLY file data.
+///
+/// # Returns
+/// A `Result` containing the vector of parsed `PlyGaussian` structs or an error.
+pub fn load_ply_from_bytes_selective(data: &[u8]) -> Result<Vec<PlyGaussian>, Ply2SplatError> {
+    load_ply_from_bytes_selective_with_options(data, &LoadOptions::default())
+}
+
+/// Loads PLY data like `load_ply_from_bytes_selective`, using the given `LoadOptions`.
+///
+/// The element, `strict` and `allow_missing` options are honored by the selective reader.
+/// Capturing `f_rest_*` coefficients or a mask property needs the generic parser, so
+/// `sh_rest` and `mask_property` fall back to it. The result is identical to
+/// `load_ply_from_bytes_with_options` in all cases.
+///
+/// # Arguments
+/// * `data` - A byte slice containing PLY file data.
+/// * `options` - Controls which element is read and how strictly.
+///
+/// # Returns
+/// A `Result` containing the vector of parsed `PlyGaussian` structs or an error.
+pub fn load_ply_from_bytes_selective_with_options(
+    data: &[u8],
+    options: &LoadOptions,
+) -> Result<Vec<PlyGaussian>, Ply2SplatError> {
+    if options.sh_rest || options.mask_property.is_some() {
+        return load_ply_from_bytes_with_options(data, options);
+    }
+
+    let element = options.element_name();
+    let mut cursor = Cursor::new(data);
+    let header = Parser::<PlyGaussian>::new()
+        .read_header(&mut cursor)
+        .map_err(|e| Ply2SplatError::Parse(format!("data: {e}")))?;
+    if !header.elements.contains_key(element) {
+        return Err(missing_element(&header, element));
+    }
+    if !options.allow_missing {
+        validate_gaussian_element(&header, element)?;
+    }
+
+    match VertexLayout::from_header(&header, element)? {
+        Some(layout) => {
+            // The fallback below warns on its own, so only warn when decoding here
+            warn_unsupported_property_types(&header, element, &options.on_warning);
+            layout.decode_all(&data[cursor.position() as usize..], options)
+        }
+        None => load_ply_from_bytes_with_options(data, options),
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::{LoadWarning, WarningHandler, load_ply_from_bytes};
+
+    /// Builds a binary little-endian PLY with the 14 standard properties interleaved with
+    /// `extra` unused float properties per vertex.
+    pub(crate) fn binary_ply(vertices: &[[f32; 14]], extra: usize) -> Vec<u8> {
+        const NAMES: [&str; 14] = [
+            "x", "y", "z", "f_dc_0", "f_dc_1", "f_dc_2", "opacity", "scale_0", "scale_1",
+            "scale_2", "rot_0", "rot_1", "rot_2", "rot_3",
+        ];
+        let mut header = format!(
+            "ply\nformat binary_little_endian 1.0\nelement vertex {}\n",
+            vertices.len()
+        );
+        for name in &NAMES[..3] {
+            header += &format!("property float {name}\n");
+        }
+        for i in 0..extra {
+            header += &format!("property float extra_{i}\n");
+        }
+        header += "property uchar flags\n";
+        for name in &NAMES[3..] {
+            header += &format!("property float {name}\n");
+        }
+        header += "end_header\n";
+
+        let mut data = header.into_bytes();
+        for v in vertices {
+            for value in &v[..3] {
+                data.extend_from_slice(&value.to_le_bytes());
+            }
+            for i in 0..extra {
+                data.extend_from_slice(&(i as f32).to_le_bytes());
+            }
+            data.push(7);
+            for value in &v[3..] {
+                data.extend_from_slice(&value.to_le_bytes());
+            }
+        }
+        data
+    }
+
+    pub(crate) fn sample_vertices(n: usize) -> Vec<[f32; 14]> {
+        (0..n)
+            .map(|i| std::array::from_fn(|j| (i * 14 + j) as f32 * 0.25 - 3.0))
+            .collect()
+    }
+
+    pub(crate) fn assert_same(a: &[PlyGaussian], b: &[PlyGaussian]) {
+        assert_eq!(a.len(), b.len());
+        for (a, b) in a.iter().zip(b) {
+            assert_eq!(format!("{a:?}"), format!("{b:?}"));
+        }
+    }
+
+    #[test]
+    fn test_selective_matches_full_parse() {
+        let data = binary_ply(&sample_vertices(50), 46);
+        let full = load_ply_from_bytes(&data).unwrap();
+        let selective = load_ply_from_bytes_selective(&data).unwrap();
+        assert_same(&full, &selective);
+        assert_eq!(selective[1].x, 14.0 * 0.25 - 3.0);
+    }
+
+    #[test]
+    fn test_selective_double_properties() {
+        let mut header = "ply
+format binary_big_endian 1.0
+element vertex 2
+property double x
+property float y
+property double opacity
+"
+        .to_string();
+        // The remaining Gaussian properties, all zero
+        for name in crate::GAUSSIAN_PROPERTIES
+            .iter()
+            .filter(|n| !["x", "y", "opacity"].contains(n))
+        {
+            header += &format!("property float {name}\n");
+        }
+        header += "end_header\n";
+        let mut data = header.into_bytes();
+        for (x, y, opacity) in [(1.5f64, 2.5f32, -0.75f64), (-3.0, 4.0, 8.0)] {
+            data.extend_from_slice(&x.to_be_bytes());
+            data.extend_from_slice(&y.to_be_bytes());
+            data.extend_from_slice(&opacity.to_be_bytes());
+            data.extend_from_slice(&[0; 11 * 4]);
+        }
+
+        let full = load_ply_from_bytes(&data).unwrap();
+        let selective = load_ply_from_bytes_selective(&data).unwrap();
+        assert_same(&full, &selective);
+        assert_eq!(selective[0].x, 1.5);
+        assert_eq!(selective[1].opacity, 8.0);
+    }
+
+    #[test]
+    fn test_selective_ascii_fallback() {
+        let data = b"ply
+format ascii 1.0
+element vertex 1
+property float x
+property float y
+property float z
+property float f_dc_0
+property float f_dc_1
+property float f_dc_2
+property float opacity
+property float scale_0
+property float scale_1
+property float scale_2
+property float rot_0
+property float rot_1
+property float rot_2
+property float rot_3
+end_header
+1.0 2.0 3.0 0 0 0 0 0 0 0 1 0 0 0
+";
+        let gaussians = load_ply_from_bytes_selective(data).unwrap();
+        assert_eq!(gaussians.len(), 1);
+        assert_eq!(gaussians[0].z, 3.0);
+    }
+
+    #[test]
+    fn test_selective_truncated() {
+        let mut data = binary_ply(&sample_vertices(3), 0);
+        data.truncate(data.len() - 10);
+        // The partial last record is dropped, as by the generic parser
+        let gaussians = load_ply_from_bytes_selective(&data).unwrap();
+        assert_eq!(gaussians.len(), 2);
+        assert_same(&gaussians, &load_ply_from_bytes(&data).unwrap());
+    }
+
+    #[test]
+    fn test_selective_truncated_leading_element() {
+        // A leading `camera` element of 1000 floats, of which only 3 made it into the file
+        let with_camera = |count: &str| {
+            let vertices = binary_ply(&sample_vertices(2), 0);
+            let header_len = vertices
+                .windows(11)
+                .position(|w| w == b"end_header\n")
+                .unwrap()
+                + 11;
+            let header = String::from_utf8(vertices[..header_len].to_vec()).unwrap();
+            let mut data = header
+                .replace(
+                    "element vertex",
+                    &format!("element camera {count}\nproperty float f\nelement vertex"),
+                )
+                .into_bytes();
+            data.extend_from_slice(&[0; 12]);
+            data
+        };
+
+        let data = with_camera("1000");
+        let gaussians = load_ply_from_bytes_selective(&data).unwrap();
+        assert!(gaussians.is_empty());
+        assert_same(&gaussians, &load_ply_from_bytes(&data).unwrap());
+
+        // Leading elements too large to address at all
+        let err = load_ply_from_bytes_selective(&with_camera(&usize::MAX.to_string())).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("'camera' element declares more bytes")
+        );
+    }
+
+    #[test]
+    fn test_selective_with_options() {
+        let data = binary_ply(&sample_vertices(3), 2);
+        let header_len = data.windows(11).position(|w| w == b"end_header\n").unwrap();
+        let mut renamed = String::from_utf8(data[..header_len].to_vec())
+            .unwrap()
+            .replace("element vertex", "element point")
+            .into_bytes();
+        renamed.extend_from_slice(&data[header_len..]);
+
+        let options = LoadOptions {
+            element: Some("point".to_string()),
+            ..Default::default()
+        };
+        let gaussians = load_ply_from_bytes_selective_with_options(&renamed, &options).unwrap();
+        assert_eq!(gaussians.len(), 3);
+        assert_same(&gaussians, &load_ply_from_bytes(&data).unwrap());
+        assert!(matches!(
+            load_ply_from_bytes_selective(&renamed),
+            Err(Ply2SplatError::MissingElement { .. })
+        ));
+
+        let strict = LoadOptions {
+            strict: true,
+            ..options
+        };
+        renamed.truncate(renamed.len() - 10);
+        assert!(matches!(
+            load_ply_from_bytes_selective_with_options(&renamed, &strict),
+            Err(Ply2SplatError::CountMismatch {
+                declared: 3,
+                actual: 2
+            })
+        ));
+    }
+
+    #[test]
+    fn test_selective_warns_on_unsupported_types() {
+        let mut data = b"ply
+format binary_little_endian 1.0
+element vertex 1
+property float x
+property uchar opacity
+end_header
+"
+        .to_vec();
+        data.extend_from_slice(&1.5f32.to_le_bytes());
+        data.push(200);
+
+        let collect =
+            |load: fn(&[u8], &LoadOptions) -> Result<Vec<PlyGaussian>, Ply2SplatError>| {
+                let warnings = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+                let sink = warnings.clone();
+                let options = LoadOptions {
+                    allow_missing: true,
+                    on_warning: WarningHandler::new(move |w| sink.lock().unwrap().push(w.clone())),
+                    ..Default::default()
+                };
+                let points = load(&data, &options).unwrap();
+                assert_eq!((points[0].x, points[0].opacity), (1.5, 0.0));
+                std::mem::take(&mut *warnings.lock().unwrap())
+            };
+        let selective = collect(load_ply_from_bytes_selective_with_options);
+        assert_eq!(
+            selective,
+            [LoadWarning::UnsupportedPropertyType {
+                property: "opacity".to_string(),
+                data_type: "Scalar(UChar)".to_string(),
+            }]
+        );
+        assert_eq!(selective, collect(load_ply_from_bytes_with_options));
+    }
+}
@@ -0,0 +1,245 @@
+//! Spherical harmonics color evaluation.
+
+use crate::{PlyGaussian, SH_C0};
+use ply_rs::ply::{Property, PropertyAccess};
+
+const SH_C1: f32 = 0.488_602_52;
+const SH_C2: [f32; 5] = [
+    1.092_548_4,
+    -1.092_548_4,
+    0.315_391_57,
+    -1.092_548_4,
+    0.546_274_2,
+];
+const SH_C3: [f32; 7] = [
+    -0.590_043_6,
+    2.890_611_4,
+    -0.457_045_8,
+    0.373_176_33,
+    -0.457_045_8,
+    1.445_305_7,
+    -0.590_043_6,
+];
+
+/// Selects how the base color of a splat is derived from its spherical harmonics.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ShMode {
+    /// Use only the DC component (`0.5 + SH_C0 * f_dc`). This is the standard behavior.
+    #[default]
+    DcOnly,
+    /// Evaluate all available SH bands (DC plus `f_rest_*`) for a fixed viewing direction.
+    ///
+    /// The direction points from the camera towards the scene and does not need to be
+    /// normalized. Gaussians without rest coefficients fall back to the DC color.
+    EvaluateAt([f32; 3]),
+}
+
+/// Evaluates the SH color of `p` for channel `channel` (0 = R, 1 = G, 2 = B) in the
+/// normalized direction `dir`, before the `+ 0.5` offset and clamping.
+///
+/// `f_rest` is laid out channel-major as written by the reference implementation: the first
+/// third holds all rest coefficients of R, then G, then B.
+pub(crate) fn eval_sh(p: &PlyGaussian, channel: usize, dir: [f32; 3]) -> f32 {
+    let dc = [p.f_dc_0, p.f_dc_1, p.f_dc_2][channel];
+    let mut result = SH_C0 * dc;
+
+    let per_channel = p.f_rest.len() / 3;
+    if per_channel < 3 {
+        return result;
+    }
+    let sh = &p.f_rest[channel * per_channel..(channel + 1) * per_channel];
+    let [x, y, z] = dir;
+
+    result += -SH_C1 * y * sh[0] + SH_C1 * z * sh[1] - SH_C1 * x * sh[2];
+    if per_channel < 8 {
+        return result;
+    }
+
+    let (xx, yy, zz) = (x * x, y * y, z * z);
+    let (xy, yz, xz) = (x * y, y * z, x * z);
+    result += SH_C2[0] * xy * sh[3]
+        + SH_C2[1] * yz * sh[4]
+        + SH_C2[2] * (2.0 * zz - xx - yy) * sh[5]
+        + SH_C2[3] * xz * sh[6]
+        + SH_C2[4] * (xx - yy) * sh[7];
+    if per_channel < 15 {
+        return result;
+    }
+
+    result += SH_C3[0] * y * (3.0 * xx - yy) * sh[8]
+        + SH_C3[1] * xy * z * sh[9]
+        + SH_C3[2] * y * (4.0 * zz - xx - yy) * sh[10]
+        + SH_C3[3] * z * (2.0 * zz - 3.0 * xx - 3.0 * yy) * sh[11]
+        + SH_C3[4] * x * (4.0 * zz - xx - yy) * sh[12]
+        + SH_C3[5] * z * (xx - yy) * sh[13]
+        + SH_C3[6] * x * (xx - 3.0 * yy) * sh[14];
+    result
+}
+
+/// Normalizes a viewing direction, falling back to +Z for a zero vector.
+pub(crate) fn normalize_dir(dir: [f32; 3]) -> [f32; 3] {
+    let len = (dir[0] * dir[0] + dir[1] * dir[1] + dir[2] * dir[2]).sqrt();
+    if len > 0.0 {
+        [dir[0] / len, dir[1] / len, dir[2] / len]
+    } else {
+        [0.0, 0.0, 1.0]
+    }
+}
+
+/// Number of `f_rest_*` coefficients of a degree-3 capture (15 per channel), the highest
+/// degree `eval_sh` supports.
+pub(crate) const MAX_SH_REST: usize = 45;
+
+/// Parser element that additionally captures the `f_rest_*` coefficients.
+///
+/// `PlyGaussian` itself ignores them so that the default loaders don't pay for up to
+/// 45 extra floats per vertex when they are not needed. Coefficients numbered
+/// `MAX_SH_REST` or higher are ignored, so a header cannot make every vertex allocate an
+/// arbitrarily large buffer.
+#[derive(Clone)]
+pub(crate) struct PlyGaussianWithRest(pub(crate) PlyGaussian);
+
+impl PropertyAccess for PlyGaussianWithRest {
+    fn new() -> Self {
+        Self(PlyGaussian::default())
+    }
+
+    fn set_property(&mut self, key: String, property: Property) {
+        let index = key
+            .strip_prefix("f_rest_")
+            .and_then(|n| n.parse::<usize>().ok());
+        match (index, crate::property_as_f32(&property)) {
+            (Some(i), _) if i >= MAX_SH_REST => {}
+            (Some(i), Some(v)) => {
+                let rest = &mut self.0.f_rest;
+                if rest.len() <= i {
+                    rest.resize(i + 1, 0.0);
+                }
+                rest[i] = v;
+            }
+            (None, _) => self.0.set_property(key, property),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ConvertOptions, LoadOptions, SplatPoint, load_ply_from_bytes_with_options, ply_to_splat,
+        ply_to_splat_with_options, splats_to_bytes,
+    };
+
+    fn gaussian_with_rest() -> PlyGaussian {
+        let mut f_rest = vec![0.0; 45];
+        // First degree-1 coefficient of each channel (multiplies -SH_C1 * y)
+        f_rest[0] = 1.0;
+        f_rest[15] = -1.0;
+        f_rest[30] = 0.5;
+        PlyGaussian {
+            x: 1.0,
+            opacity: 2.0,
+            f_dc_0: 0.3,
+            f_dc_1: -0.2,
+            f_dc_2: 0.1,
+            rot_0: 1.0,
+            f_rest,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_dc_only_is_unchanged() {
+        let points = vec![gaussian_with_rest(), PlyGaussian::default()];
+        let default = ply_to_splat(points.clone(), true);
+        let dc_only = ply_to_splat_with_options(
+            points,
+            &ConvertOptions {
+                sh_mode: ShMode::DcOnly,
+                ..Default::default()
+            },
+        );
+        assert_eq!(splats_to_bytes(&default), splats_to_bytes(&dc_only));
+    }
+
+    #[test]
+    fn test_evaluate_degree_one() {
+        let p = gaussian_with_rest();
+        let options = ConvertOptions {
+            sh_mode: ShMode::EvaluateAt([0.0, -2.0, 0.0]),
+            ..Default::default()
+        };
+        let (splat, _) = SplatPoint::from_ply_with_options(&p, &options);
+
+        // dir = (0, -1, 0): each channel gains SH_C1 * f_rest[first]
+        let expected =
+            |dc: f32, rest: f32| ((0.5 + SH_C0 * dc + SH_C1 * rest).clamp(0.0, 1.0) * 255.0) as u8;
+        assert_eq!(splat.color[0], expected(0.3, 1.0));
+        assert_eq!(splat.color[1], expected(-0.2, -1.0));
+        assert_eq!(splat.color[2], expected(0.1, 0.5));
+
+        // Looking along the x axis, the degree-1 y term vanishes and only DC remains.
+        let options = ConvertOptions {
+            sh_mode: ShMode::EvaluateAt([1.0, 0.0, 0.0]),
+            ..Default::default()
+        };
+        let (side, _) = SplatPoint::from_ply_with_options(&p, &options);
+        let (dc, _) = SplatPoint::from_ply(&p);
+        assert_eq!(side.color, dc.color);
+    }
+
+    #[test]
+    fn test_load_f_rest() {
+        let ply_content = b"ply
+format ascii 1.0
+element vertex 1
+property float x
+property float f_dc_0
+property float f_rest_0
+property float f_rest_1
+property float f_rest_2
+end_header
+1.0 0.5 0.1 0.2 0.3
+";
+        let options = LoadOptions {
+            sh_rest: true,
+            allow_missing: true,
+            ..Default::default()
+        };
+        let with_rest = load_ply_from_bytes_with_options(ply_content, &options).unwrap();
+        assert_eq!(with_rest[0].x, 1.0);
+        assert_eq!(with_rest[0].f_rest, vec![0.1, 0.2, 0.3]);
+
+        let options = LoadOptions {
+            allow_missing: true,
+            ..Default::default()
+        };
+        let without = load_ply_from_bytes_with_options(ply_content, &options).unwrap();
+        assert!(without[0].f_rest.is_empty());
+    }
+
+    #[test]
+    fn test_out_of_range_f_rest_is_ignored() {
+        let ply_content = b"ply
+format ascii 1.0
+element vertex 1
+property float x
+property float f_rest_0
+property float f_rest_44
+property float f_rest_45
+property float f_rest_4000000000
+end_header
+1.0 0.1 0.2 0.3 0.4
+";
+        let options = LoadOptions {
+            sh_rest: true,
+            allow_missing: true,
+            ..Default::default()
+        };
+        let points = load_ply_from_bytes_with_options(ply_content, &options).unwrap();
+        assert_eq!(points[0].f_rest.len(), MAX_SH_REST);
+        assert_eq!(points[0].f_rest[0], 0.1);
+        assert_eq!(points[0].f_rest[44], 0.2);
+    }
+}
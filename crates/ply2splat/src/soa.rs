@@ -0,0 +1,175 @@
+//! Structure-of-arrays layout for GPU pipelines that read attributes separately.
+//!
+//! ## Layout
+//!
+//! All integers are little-endian. The file starts with an 80-byte header followed by four
+//! contiguous planes, each holding one attribute of every splat in the same order:
+//!
+//! | Offset | Size | Content                                               |
+//! |--------|------|-------------------------------------------------------|
+//! | 0      | 4    | Magic `SPSA`                                          |
+//! | 4      | 4    | Format version (`u32`, currently 1)                   |
+//! | 8      | 4    | Splat count `n` (`u32`)                               |
+//! | 12     | 4    | Reserved, 0                                           |
+//! | 16     | 16   | Positions plane: offset and length in bytes (`u64`s)  |
+//! | 32     | 16   | Scales plane: offset and length                       |
+//! | 48     | 16   | Colors plane: offset and length                       |
+//! | 64     | 16   | Rotations plane: offset and length                    |
+//!
+//! The planes follow in that order without gaps, starting at byte 80:
+//!
+//! | Plane     | Offset        | Length   | Element                            |
+//! |-----------|---------------|----------|------------------------------------|
+//! | Positions | 80            | `n * 12` | `[f32; 3]`, as `SplatPoint::pos`   |
+//! | Scales    | `80 + n * 12` | `n * 12` | `[f32; 3]`, as `SplatPoint::scale` |
+//! | Colors    | `80 + n * 24` | `n * 4`  | `[u8; 4]`, as `SplatPoint::color`  |
+//! | Rotations | `80 + n * 28` | `n * 4`  | `[u8; 4]`, as `SplatPoint::rot`    |
+//!
+//! Every plane starts at a multiple of 4 bytes. Readers should use the offsets from the
+//! header rather than computing them.
+
+use crate::SplatPoint;
+use anyhow::{Result, bail};
+
+/// Magic bytes at the start of SoA data.
+pub const SOA_MAGIC: [u8; 4] = *b"SPSA";
+/// Current version of the SoA layout.
+pub const SOA_VERSION: u32 = 1;
+/// Size of the SoA header in bytes.
+pub const SOA_HEADER_SIZE: usize = 80;
+
+/// Converts interleaved splats into the SoA layout described in the module documentation.
+pub fn to_soa(splats: &[SplatPoint]) -> Vec<u8> {
+    let n = splats.len();
+    let lengths = [n * 12, n * 12, n * 4, n * 4];
+    let mut out = Vec::with_capacity(SOA_HEADER_SIZE + size_of_val(splats));
+
+    out.extend_from_slice(&SOA_MAGIC);
+    out.extend_from_slice(&SOA_VERSION.to_le_bytes());
+    out.extend_from_slice(&(n as u32).to_le_bytes());
+    out.extend_from_slice(&0u32.to_le_bytes());
+    let mut offset = SOA_HEADER_SIZE;
+    for length in lengths {
+        out.extend_from_slice(&(offset as u64).to_le_bytes());
+        out.extend_from_slice(&(length as u64).to_le_bytes());
+        offset += length;
+    }
+
+    for s in splats {
+        out.extend_from_slice(bytemuck::cast_slice(&s.pos));
+    }
+    for s in splats {
+        out.extend_from_slice(bytemuck::cast_slice(&s.scale));
+    }
+    for s in splats {
+        out.extend_from_slice(&s.color);
+    }
+    for s in splats {
+        out.extend_from_slice(&s.rot);
+    }
+    out
+}
+
+/// Reads SoA data written by `to_soa` back into interleaved splats.
+pub fn from_soa(data: &[u8]) -> Result<Vec<SplatPoint>> {
+    if data.len() < SOA_HEADER_SIZE {
+        bail!("Invalid SoA data: {} bytes is too short", data.len());
+    }
+    if data[..4] != SOA_MAGIC {
+        bail!("Invalid SoA data: missing SPSA magic");
+    }
+    let word = |at: usize| u32::from_le_bytes(data[at..at + 4].try_into().unwrap());
+    let version = word(4);
+    if version != SOA_VERSION {
+        bail!("Unsupported SoA version {version}");
+    }
+    let n = word(8) as usize;
+
+    let mut planes = [&data[..0]; 4];
+    for (i, (plane, element_size)) in planes.iter_mut().zip([12, 12, 4, 4]).enumerate() {
+        let at = 16 + i * 16;
+        let offset = u64::from_le_bytes(data[at..at + 8].try_into().unwrap());
+        let length = u64::from_le_bytes(data[at + 8..at + 16].try_into().unwrap());
+        // Checked, as the products and the u64 fields can overflow a 32-bit usize
+        if n.checked_mul(element_size).map(|expected| expected as u64) != Some(length) {
+            bail!("Invalid SoA data: plane {i} has {length} bytes for {n} splats");
+        }
+        let Some(bytes) = usize::try_from(offset)
+            .ok()
+            .zip(usize::try_from(length).ok())
+            .and_then(|(offset, length)| data.get(offset..offset.checked_add(length)?))
+        else {
+            bail!("Invalid SoA data: plane {i} lies outside the data");
+        };
+        *plane = bytes;
+    }
+
+    let [positions, scales, colors, rotations] = planes;
+    let vec3 = |plane: &[u8], i: usize| -> [f32; 3] {
+        bytemuck::pod_read_unaligned(&plane[i * 12..i * 12 + 12])
+    };
+    Ok((0..n)
+        .map(|i| SplatPoint {
+            pos: vec3(positions, i),
+            scale: vec3(scales, i),
+            color: colors[i * 4..i * 4 + 4].try_into().unwrap(),
+            rot: rotations[i * 4..i * 4 + 4].try_into().unwrap(),
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::splats_to_bytes;
+
+    fn splats() -> Vec<SplatPoint> {
+        (0..5)
+            .map(|i| SplatPoint {
+                pos: [i as f32, -(i as f32) * 0.5, 2.0],
+                scale: [0.1 * i as f32, 1.0, 3.5],
+                color: [i as u8, 10, 20, 255 - i as u8],
+                rot: [128 + i as u8, 128, 127, 3],
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let splats = splats();
+        let soa = to_soa(&splats);
+        assert_eq!(soa.len(), SOA_HEADER_SIZE + splats.len() * 32);
+        // The colors plane starts after positions and scales
+        let colors_offset = u64::from_le_bytes(soa[48..56].try_into().unwrap()) as usize;
+        assert_eq!(colors_offset, SOA_HEADER_SIZE + 5 * 24);
+        assert_eq!(&soa[colors_offset..colors_offset + 4], &splats[0].color);
+
+        let back = from_soa(&soa).unwrap();
+        assert_eq!(splats_to_bytes(&back), splats_to_bytes(&splats));
+
+        assert!(from_soa(&to_soa(&[])).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_invalid() {
+        let soa = to_soa(&splats());
+        assert!(from_soa(&soa[..soa.len() - 1]).is_err());
+        assert!(from_soa(&soa[..40]).is_err());
+        let mut bad_magic = soa.clone();
+        bad_magic[0] = b'X';
+        assert!(from_soa(&bad_magic).is_err());
+    }
+
+    #[test]
+    fn test_huge_count() {
+        // A count whose plane sizes wrap a 32-bit usize must not pass the length checks
+        let mut soa = to_soa(&[]);
+        soa[8..12].copy_from_slice(&u32::MAX.to_le_bytes());
+        for (i, element_size) in [12u64, 12, 4, 4].into_iter().enumerate() {
+            let wrapped = (u32::MAX as u64 * element_size) % (1 << 32);
+            let at = 16 + i * 16 + 8;
+            soa[at..at + 8].copy_from_slice(&wrapped.to_le_bytes());
+        }
+        assert!(from_soa(&soa).is_err());
+    }
+}
@@ -0,0 +1,169 @@
+//! Summary statistics for sanity-checking converted scenes.
+
+use crate::SplatPoint;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use std::fmt;
+
+/// Number of buckets in `SplatStats::opacity_histogram`.
+pub const OPACITY_BUCKETS: usize = 10;
+
+/// Summary of a set of splats.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SplatStats {
+    /// Number of splats.
+    pub count: usize,
+    /// Minimum corner of the axis-aligned bounding box of the splat centers.
+    ///
+    /// Infinite when there are no splats, so that `min > max`.
+    pub min: [f32; 3],
+    /// Maximum corner of the bounding box.
+    pub max: [f32; 3],
+    /// Mean scale per axis.
+    pub mean_scale: [f32; 3],
+    /// Largest scale along any axis.
+    pub max_scale: f32,
+    /// Mean alpha in `[0, 1]`.
+    pub mean_opacity: f32,
+    /// Number of splats per alpha bucket; bucket `i` covers `[i / 10, (i + 1) / 10)` and the
+    /// last bucket includes 1.
+    pub opacity_histogram: [usize; OPACITY_BUCKETS],
+}
+
+/// Running totals combined by the parallel reduction.
+#[derive(Clone, Copy)]
+struct Accumulator {
+    count: usize,
+    min: [f32; 3],
+    max: [f32; 3],
+    scale_sum: [f64; 3],
+    max_scale: f32,
+    opacity_sum: f64,
+    histogram: [usize; OPACITY_BUCKETS],
+}
+
+impl Accumulator {
+    const EMPTY: Self = Self {
+        count: 0,
+        min: [f32::INFINITY; 3],
+        max: [f32::NEG_INFINITY; 3],
+        scale_sum: [0.0; 3],
+        max_scale: 0.0,
+        opacity_sum: 0.0,
+        histogram: [0; OPACITY_BUCKETS],
+    };
+
+    fn add(mut self, s: &SplatPoint) -> Self {
+        self.count += 1;
+        for i in 0..3 {
+            self.min[i] = self.min[i].min(s.pos[i]);
+            self.max[i] = self.max[i].max(s.pos[i]);
+            self.scale_sum[i] += s.scale[i] as f64;
+            self.max_scale = self.max_scale.max(s.scale[i]);
+        }
+        self.opacity_sum += s.color[3] as f64 / 255.0;
+        let bucket = (s.color[3] as usize * OPACITY_BUCKETS / 255).min(OPACITY_BUCKETS - 1);
+        self.histogram[bucket] += 1;
+        self
+    }
+
+    #[cfg(feature = "parallel")]
+    fn merge(mut self, other: Self) -> Self {
+        self.count += other.count;
+        for i in 0..3 {
+            self.min[i] = self.min[i].min(other.min[i]);
+            self.max[i] = self.max[i].max(other.max[i]);
+            self.scale_sum[i] += other.scale_sum[i];
+        }
+        self.max_scale = self.max_scale.max(other.max_scale);
+        self.opacity_sum += other.opacity_sum;
+        for (a, b) in self.histogram.iter_mut().zip(other.histogram) {
+            *a += b;
+        }
+        self
+    }
+}
+
+/// Computes bounds, scale and opacity summaries of `splats`.
+pub fn compute_stats(splats: &[SplatPoint]) -> SplatStats {
+    #[cfg(feature = "parallel")]
+    let acc = splats
+        .par_iter()
+        .fold(|| Accumulator::EMPTY, Accumulator::add)
+        .reduce(|| Accumulator::EMPTY, Accumulator::merge);
+    #[cfg(not(feature = "parallel"))]
+    let acc = splats.iter().fold(Accumulator::EMPTY, Accumulator::add);
+
+    let n = acc.count.max(1) as f64;
+    SplatStats {
+        count: acc.count,
+        min: acc.min,
+        max: acc.max,
+        mean_scale: acc.scale_sum.map(|s| (s / n) as f32),
+        max_scale: acc.max_scale,
+        mean_opacity: (acc.opacity_sum / n) as f32,
+        opacity_histogram: acc.histogram,
+    }
+}
+
+impl fmt::Display for SplatStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Splats: {}", self.count)?;
+        writeln!(f, "Bounds min: {:?}", self.min)?;
+        writeln!(f, "Bounds max: {:?}", self.max)?;
+        writeln!(f, "Mean scale: {:?}", self.mean_scale)?;
+        writeln!(f, "Max scale: {}", self.max_scale)?;
+        writeln!(f, "Mean opacity: {:.3}", self.mean_opacity)?;
+        write!(f, "Opacity histogram:")?;
+        for (i, count) in self.opacity_histogram.iter().enumerate() {
+            write!(
+                f,
+                "\n  {:.1}-{:.1}: {}",
+                i as f32 / OPACITY_BUCKETS as f32,
+                (i + 1) as f32 / OPACITY_BUCKETS as f32,
+                count
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn splat(pos: [f32; 3], scale: f32, alpha: u8) -> SplatPoint {
+        SplatPoint {
+            pos,
+            scale: [scale; 3],
+            color: [255, 255, 255, alpha],
+            rot: [255, 128, 128, 128],
+        }
+    }
+
+    #[test]
+    fn test_bounds_and_summaries() {
+        let splats = [
+            splat([1.0, -2.0, 3.0], 0.5, 255),
+            splat([-4.0, 5.0, 0.0], 1.5, 0),
+            splat([2.5, 0.0, -6.0], 1.0, 128),
+            splat([0.0, 1.0, 1.0], 1.0, 26),
+        ];
+        let stats = compute_stats(&splats);
+        assert_eq!(stats.count, 4);
+        assert_eq!(stats.min, [-4.0, -2.0, -6.0]);
+        assert_eq!(stats.max, [2.5, 5.0, 3.0]);
+        assert_eq!(stats.mean_scale, [1.0; 3]);
+        assert_eq!(stats.max_scale, 1.5);
+        assert_eq!(stats.opacity_histogram, [1, 1, 0, 0, 0, 1, 0, 0, 0, 1]);
+        assert!((stats.mean_opacity - (255 + 128 + 26) as f32 / 255.0 / 4.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_empty() {
+        let stats = compute_stats(&[]);
+        assert_eq!(stats.count, 0);
+        assert!(stats.min[0] > stats.max[0]);
+        assert_eq!(stats.opacity_histogram, [0; OPACITY_BUCKETS]);
+    }
+}
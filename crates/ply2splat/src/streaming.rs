@@ -0,0 +1,289 @@
+//! Chunked conversion that never holds the whole scene in memory.
+//!
+//! Without sorting every splat only depends on its own Gaussian, so vertices can be read,
+//! converted and written in fixed-size chunks. Peak memory is then bounded by the chunk size
+//! instead of the file size.
+
+use crate::{
+    ConversionPool, ConvertOptions, Ply2SplatError, PlyGaussian, ShMode, SplatPoint,
+    convert_in_current_pool, missing_element, read_elements, sh, validate::check_vertex_count,
+    validate_gaussian_header, warn_unsupported_property_types,
+};
+use anyhow::Result;
+use ply_rs::parser::Parser;
+use ply_rs::ply::{DefaultElement, PropertyAccess};
+use std::io::{BufRead, BufReader, Read, Write};
+
+/// Number of vertices converted per chunk.
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Counts of a streaming conversion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StreamedCounts {
+    /// Gaussians read from the payload, fewer than declared if the input was truncated.
+    pub vertices: usize,
+    /// Splats written, fewer than `vertices` if filters dropped some.
+    pub splats: usize,
+}
+
+/// Streams the `vertex` element through the conversion in chunks of `CHUNK_SIZE`, each
+/// converted inside `pool`.
+fn stream_vertices<E: PropertyAccess, R: BufRead, W: Write>(
+    reader: &mut R,
+    writer: &mut W,
+    options: &ConvertOptions,
+    pool: &ConversionPool,
+    into_gaussian: fn(E) -> PlyGaussian,
+) -> Result<StreamedCounts> {
+    let header = Parser::<E>::new()
+        .read_header(reader)
+        .map_err(|e| Ply2SplatError::Parse(format!("data: {e}")))?;
+    if !header.elements.contains_key("vertex") {
+        return Err(missing_element(&header, "vertex").into());
+    }
+    validate_gaussian_header(&header)?;
+    warn_unsupported_property_types(&header, "vertex", &options.on_warning);
+
+    let mut written = 0;
+    let mut chunk = Vec::with_capacity(CHUNK_SIZE);
+    let mut flush = |chunk: &mut Vec<PlyGaussian>, writer: &mut W| -> Result<()> {
+        let chunk = std::mem::take(chunk);
+        let splats = pool.install(|| convert_in_current_pool(chunk, options, None));
+        writer.write_all(bytemuck::cast_slice::<SplatPoint, u8>(&splats))?;
+        written += splats.len();
+        Ok(())
+    };
+
+    for element_def in header.elements.values() {
+        if element_def.name != "vertex" {
+            // Elements before `vertex` have to be consumed to reach its records.
+            read_elements::<DefaultElement, _, anyhow::Error>(
+                reader,
+                header.encoding,
+                element_def,
+                "data",
+                |_| Ok(()),
+            )?;
+            continue;
+        }
+
+        let read = read_elements(reader, header.encoding, element_def, "data", |e| {
+            chunk.push(into_gaussian(e));
+            if chunk.len() == CHUNK_SIZE {
+                flush(&mut chunk, writer)?;
+            }
+            Ok::<_, anyhow::Error>(())
+        })?;
+        flush(&mut chunk, writer)?;
+        check_vertex_count(element_def.count, read, false, &options.on_warning)?;
+        return Ok(StreamedCounts {
+            vertices: read,
+            splats: written,
+        });
+    }
+    unreachable!("the header was checked for a 'vertex' element")
+}
+
+/// Converts a PLY stream into SPLAT records written to `writer`, using the given options.
+///
+/// With `options.sort == false` the input is processed in chunks and never fully held in
+/// memory; elements after `vertex` are not read. Sorting needs the whole scene, so in that
+/// case all vertices are buffered first. The output is identical to
+/// `ply_to_splat_with_options` followed by `splats_to_bytes`. Like `load_ply_from_bytes`,
+/// fails with `Ply2SplatError::MissingElement` if there is no `vertex` element and with
+/// `Ply2SplatError::MissingProperties` if it lacks an expected property.
+///
+/// # Returns
+/// The number of Gaussians read and of splats written.
+pub fn convert_ply_to_splat_streaming_with_options<R: Read, W: Write>(
+    reader: R,
+    mut writer: W,
+    options: &ConvertOptions,
+) -> Result<StreamedCounts> {
+    let mut reader = BufReader::with_capacity(10 * 1024 * 1024, reader);
+    // Built once, as entering a pool is cheap but creating one spawns its threads
+    let pool = ConversionPool::new(options.num_threads);
+
+    if options.sort {
+        let load_options = crate::LoadOptions {
+            sh_rest: options.sh_mode != ShMode::DcOnly,
+            on_warning: options.on_warning.clone(),
+            ..Default::default()
+        };
+        let points = crate::read_ply_gaussians(&mut reader, "data", &load_options)?;
+        let vertices = points.len();
+        let splats = pool.install(|| convert_in_current_pool(points, options, None));
+        writer.write_all(bytemuck::cast_slice(&splats))?;
+        return Ok(StreamedCounts {
+            vertices,
+            splats: splats.len(),
+        });
+    }
+
+    if options.sh_mode == ShMode::DcOnly {
+        stream_vertices::<PlyGaussian, _, _>(&mut reader, &mut writer, options, &pool, |p| p)
+    } else {
+        stream_vertices::<sh::PlyGaussianWithRest, _, _>(
+            &mut reader,
+            &mut writer,
+            options,
+            &pool,
+            |p| p.0,
+        )
+    }
+}
+
+/// Converts a PLY stream into SPLAT records written to `writer`.
+///
+/// See `convert_ply_to_splat_streaming_with_options`; with `sort == false` memory use stays
+/// bounded regardless of the input size.
+///
+/// # Arguments
+/// * `reader` - Source of the PLY data.
+/// * `writer` - Destination for the 32-byte SPLAT records.
+/// * `sort` - If true, sorts the splats by importance (requires buffering the scene).
+///
+/// # Returns
+/// The number of splats written.
+pub fn convert_ply_to_splat_streaming<R: Read, W: Write>(
+    reader: R,
+    writer: W,
+    sort: bool,
+) -> Result<usize> {
+    let counts = convert_ply_to_splat_streaming_with_options(
+        reader,
+        writer,
+        &ConvertOptions {
+            sort,
+            ..Default::default()
+        },
+    )?;
+    Ok(counts.splats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::selective::tests::binary_ply;
+    use crate::{
+        convert, load_ply_from_bytes_with_options, ply_to_splat_with_options, splats_to_bytes,
+    };
+
+    fn sample_vertices(n: usize) -> Vec<[f32; 14]> {
+        (0..n)
+            .map(|i| std::array::from_fn(|j| ((i * 7 + j * 3) % 23) as f32 * 0.1 - 1.0))
+            .collect()
+    }
+
+    #[test]
+    fn test_streaming_matches_buffered() {
+        // Spans several chunks with a partial last one
+        let data = binary_ply(&sample_vertices(2 * CHUNK_SIZE + 123), 5);
+
+        for sort in [false, true] {
+            let mut out = Vec::new();
+            let count = convert_ply_to_splat_streaming(&data[..], &mut out, sort).unwrap();
+            let (expected, expected_count) = convert(&data, sort).unwrap();
+            assert_eq!(count, expected_count);
+            assert_eq!(out, expected);
+
+            // A dedicated pool, entered once per stream, gives the same bytes
+            let options = ConvertOptions {
+                sort,
+                num_threads: 2,
+                ..Default::default()
+            };
+            let mut pooled = Vec::new();
+            convert_ply_to_splat_streaming_with_options(&data[..], &mut pooled, &options).unwrap();
+            assert_eq!(pooled, expected);
+        }
+    }
+
+    #[test]
+    fn test_streaming_ascii_with_leading_element() {
+        let data = b"ply
+format ascii 1.0
+element camera 1
+property float fx
+element vertex 2
+property float x
+property float y
+property float z
+property float f_dc_0
+property float f_dc_1
+property float f_dc_2
+property float opacity
+property float scale_0
+property float scale_1
+property float scale_2
+property float rot_0
+property float rot_1
+property float rot_2
+property float rot_3
+property float f_rest_0
+end_header
+500.0
+1.0 0 0 0 0 0 2.0 0 0 0 1 0 0 0 0.5
+-1.0 0 0 0 0 0 -2.0 0 0 0 1 0 0 0 0.25
+";
+        let options = ConvertOptions {
+            sort: false,
+            sh_mode: ShMode::EvaluateAt([0.0, 0.0, 1.0]),
+            ..Default::default()
+        };
+        let mut out = Vec::new();
+        let counts =
+            convert_ply_to_splat_streaming_with_options(&data[..], &mut out, &options).unwrap();
+        assert_eq!(
+            counts,
+            StreamedCounts {
+                vertices: 2,
+                splats: 2
+            }
+        );
+
+        let points = load_ply_from_bytes_with_options(
+            data,
+            &crate::LoadOptions {
+                sh_rest: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            out,
+            splats_to_bytes(&ply_to_splat_with_options(points, &options))
+        );
+    }
+
+    #[test]
+    fn test_streaming_counts_and_errors() {
+        // A truncated payload reports the Gaussians actually read, not the declared count
+        let mut data = binary_ply(&sample_vertices(5), 0);
+        data.truncate(data.len() - 10);
+        for sort in [false, true] {
+            let options = ConvertOptions {
+                sort,
+                ..Default::default()
+            };
+            let counts =
+                convert_ply_to_splat_streaming_with_options(&data[..], std::io::sink(), &options)
+                    .unwrap();
+            assert_eq!(
+                counts,
+                StreamedCounts {
+                    vertices: 4,
+                    splats: 4
+                }
+            );
+        }
+
+        let no_vertex = b"ply\nformat ascii 1.0\nelement point 0\nproperty float x\nend_header\n";
+        let err =
+            convert_ply_to_splat_streaming(&no_vertex[..], std::io::sink(), false).unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<Ply2SplatError>(),
+            Some(Ply2SplatError::MissingElement { name, .. }) if name == "vertex"
+        ));
+    }
+}
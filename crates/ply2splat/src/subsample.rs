@@ -0,0 +1,97 @@
+//! Seeded random subsampling for quick previews of large scenes.
+
+use crate::PlyGaussian;
+
+/// SplitMix64 generator.
+///
+/// Implemented here rather than taken from a crate so that a seed selects the same splats
+/// across releases and platforms.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform value in `0..bound`, using the widening multiply to avoid modulo bias.
+    fn below(&mut self, bound: usize) -> usize {
+        ((self.next_u64() as u128 * bound as u128) >> 64) as usize
+    }
+}
+
+/// Indices of `target` Gaussians out of `len`, chosen uniformly at random and sorted.
+fn sample_indices(len: usize, target: usize, seed: u64) -> Vec<usize> {
+    let mut rng = SplitMix64(seed);
+    let mut indices: Vec<usize> = (0..len).collect();
+    // Partial Fisher-Yates shuffle: the first `target` slots end up a uniform sample
+    for i in 0..target {
+        let j = i + rng.below(len - i);
+        indices.swap(i, j);
+    }
+    indices.truncate(target);
+    indices.sort_unstable();
+    indices
+}
+
+/// Randomly keeps `target` Gaussians, in their original order.
+///
+/// The selection depends only on `seed` and the number of Gaussians, so the same input and
+/// seed always yield the same output. Returns `points` unchanged if `target >= points.len()`.
+pub fn subsample_to(points: Vec<PlyGaussian>, target: usize, seed: u64) -> Vec<PlyGaussian> {
+    if target >= points.len() {
+        return points;
+    }
+    let mut keep = vec![false; points.len()];
+    for i in sample_indices(points.len(), target, seed) {
+        keep[i] = true;
+    }
+    points
+        .into_iter()
+        .zip(keep)
+        .filter_map(|(p, keep)| keep.then_some(p))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn points(n: usize) -> Vec<PlyGaussian> {
+        (0..n)
+            .map(|i| PlyGaussian {
+                x: i as f32,
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    fn xs(points: &[PlyGaussian]) -> Vec<f32> {
+        points.iter().map(|p| p.x).collect()
+    }
+
+    #[test]
+    fn test_deterministic() {
+        let a = subsample_to(points(1000), 100, 42);
+        let b = subsample_to(points(1000), 100, 42);
+        assert_eq!(a.len(), 100);
+        assert_eq!(xs(&a), xs(&b));
+        // Original order is preserved
+        assert!(xs(&a).is_sorted());
+        // Another seed picks other splats
+        assert_ne!(xs(&a), xs(&subsample_to(points(1000), 100, 7)));
+        // Fixed indices guard against the generator changing between releases
+        assert_eq!(sample_indices(10, 3, 42), [2, 4, 7]);
+    }
+
+    #[test]
+    fn test_length() {
+        for (len, target) in [(0, 5), (10, 0), (10, 3), (10, 10), (10, 25)] {
+            assert_eq!(subsample_to(points(len), target, 1).len(), target.min(len));
+        }
+        assert_eq!(xs(&subsample_to(points(4), 4, 1)), [0.0, 1.0, 2.0, 3.0]);
+    }
+}
@@ -0,0 +1,144 @@
+//! World-aligned tiling for serving large scenes as map tiles.
+//!
+//! Tiles form a fixed grid of `tile_size` x `tile_size` cells in the XY plane anchored at the
+//! world origin, so the same cell covers the same area in every conversion and tiles of
+//! overlapping captures line up. Cell `(X, Y)` covers `[X * size, (X + 1) * size)` on the x
+//! axis and likewise on y. Each splat belongs to exactly the cell containing its center, even
+//! if its extent crosses the cell boundary.
+
+use crate::{SplatPoint, splats_to_bytes};
+use anyhow::{Context, Result, bail};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// The splats falling into one grid cell.
+#[derive(Debug, Clone)]
+pub struct GeoTile {
+    /// Cell index along the x axis.
+    pub x: i64,
+    /// Cell index along the y axis.
+    pub y: i64,
+    /// Splats whose center lies in the cell, in their input order.
+    pub splats: Vec<SplatPoint>,
+}
+
+impl GeoTile {
+    /// File name of the tile, `tile_X_Y.splat`.
+    pub fn file_name(&self) -> String {
+        format!("tile_{}_{}.splat", self.x, self.y)
+    }
+}
+
+/// Partitions splats into world-aligned grid cells of `tile_size` units.
+///
+/// Splats keep their relative order within a tile, so sorted input yields sorted tiles.
+/// Empty cells are omitted and the tiles are ordered by `(x, y)`.
+///
+/// # Panics
+/// Panics if `tile_size` is not positive.
+pub fn geotile(splats: &[SplatPoint], tile_size: f32) -> Vec<GeoTile> {
+    assert!(tile_size > 0.0, "tile_size must be positive");
+    let mut cells: BTreeMap<(i64, i64), Vec<SplatPoint>> = BTreeMap::new();
+    for s in splats {
+        let x = (s.pos[0] / tile_size).floor() as i64;
+        let y = (s.pos[1] / tile_size).floor() as i64;
+        cells.entry((x, y)).or_default().push(*s);
+    }
+    cells
+        .into_iter()
+        .map(|((x, y), splats)| GeoTile { x, y, splats })
+        .collect()
+}
+
+/// Builds the `tiles.json` index describing `tiles`.
+pub fn tiles_index_json(tiles: &[GeoTile], tile_size: f32) -> String {
+    let entries: Vec<String> = tiles
+        .iter()
+        .map(|t| {
+            format!(
+                "    {{\"x\": {}, \"y\": {}, \"file\": \"{}\", \"count\": {}}}",
+                t.x,
+                t.y,
+                t.file_name(),
+                t.splats.len()
+            )
+        })
+        .collect();
+    format!(
+        "{{\n  \"tile_size\": {},\n  \"tiles\": [\n{}\n  ]\n}}\n",
+        tile_size,
+        entries.join(",\n")
+    )
+}
+
+/// Writes one `tile_X_Y.splat` file per non-empty cell plus a `tiles.json` index into `dir`.
+///
+/// The directory is created if needed.
+///
+/// # Returns
+/// The number of tiles written.
+pub fn save_geotiles<P: AsRef<Path>>(
+    dir: P,
+    splats: &[SplatPoint],
+    tile_size: f32,
+) -> Result<usize> {
+    if !(tile_size > 0.0 && tile_size.is_finite()) {
+        bail!("Tile size must be a positive number, got {tile_size}");
+    }
+    let dir = dir.as_ref();
+    std::fs::create_dir_all(dir).context("Failed to create tile directory")?;
+
+    let tiles = geotile(splats, tile_size);
+    for tile in &tiles {
+        std::fs::write(dir.join(tile.file_name()), splats_to_bytes(&tile.splats))
+            .with_context(|| format!("Failed to write tile {}", tile.file_name()))?;
+    }
+    std::fs::write(dir.join("tiles.json"), tiles_index_json(&tiles, tile_size))
+        .context("Failed to write tile index")?;
+    Ok(tiles.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{PlyGaussian, ply_to_splat};
+
+    #[test]
+    fn test_two_cells() {
+        let points = [(1.0, 1.0), (9.5, 3.0), (10.5, 2.0), (19.0, 9.9)]
+            .into_iter()
+            .map(|(x, y)| PlyGaussian {
+                x,
+                y,
+                // Large extent crossing into the neighbouring cell
+                scale_0: 2.0,
+                ..Default::default()
+            })
+            .collect();
+        let splats = ply_to_splat(points, false);
+
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(save_geotiles(dir.path(), &splats, 10.0).unwrap(), 2);
+
+        let tile_0 = std::fs::read(dir.path().join("tile_0_0.splat")).unwrap();
+        let tile_1 = std::fs::read(dir.path().join("tile_1_0.splat")).unwrap();
+        assert_eq!(tile_0, splats_to_bytes(&splats[..2]));
+        assert_eq!(tile_1, splats_to_bytes(&splats[2..]));
+
+        let index = std::fs::read_to_string(dir.path().join("tiles.json")).unwrap();
+        assert!(index.contains("\"file\": \"tile_0_0.splat\", \"count\": 2"));
+        assert!(index.contains("\"file\": \"tile_1_0.splat\", \"count\": 2"));
+    }
+
+    #[test]
+    fn test_negative_cells() {
+        let points = vec![PlyGaussian {
+            x: -0.5,
+            y: -10.0,
+            ..Default::default()
+        }];
+        let tiles = geotile(&ply_to_splat(points, false), 10.0);
+        assert_eq!(tiles.len(), 1);
+        assert_eq!(tiles[0].file_name(), "tile_-1_-1.splat");
+    }
+}
@@ -0,0 +1,206 @@
+//! Similarity transforms applied to Gaussians before quantization.
+
+use crate::{ActivationMode, ConvertOptions, PlyGaussian, SplatPoint, ply_to_splat_with_options};
+
+/// A similarity transform: uniform scale, then rotation, then translation.
+///
+/// Higher-order spherical harmonics are not rotated, so view-dependent color evaluated with
+/// `ShMode::EvaluateAt` still refers to the original frame.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transform {
+    /// Offset added after rotating.
+    pub translation: [f32; 3],
+    /// Rotation quaternion as (w, x, y, z); normalized before use.
+    pub rotation: [f32; 4],
+    /// Uniform scale factor, must be positive.
+    pub scale: f32,
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Transform {
+    /// The transform that leaves every Gaussian unchanged.
+    pub const IDENTITY: Self = Self {
+        translation: [0.0; 3],
+        rotation: [1.0, 0.0, 0.0, 0.0],
+        scale: 1.0,
+    };
+
+    /// Returns whether this transform leaves every Gaussian unchanged.
+    pub fn is_identity(&self) -> bool {
+        *self == Self::IDENTITY
+    }
+
+    fn unit_rotation(&self) -> [f32; 4] {
+        let q = self.rotation;
+        let len = (q[0] * q[0] + q[1] * q[1] + q[2] * q[2] + q[3] * q[3]).sqrt();
+        if len > 0.0 {
+            q.map(|c| c / len)
+        } else {
+            [1.0, 0.0, 0.0, 0.0]
+        }
+    }
+
+    /// Transforms a point.
+    pub fn apply_to_point(&self, p: [f32; 3]) -> [f32; 3] {
+        let [w, x, y, z] = self.unit_rotation();
+        let v = p.map(|c| c * self.scale);
+        // v + w * t + u x t with t = 2 * (u x v)
+        let t = [
+            2.0 * (y * v[2] - z * v[1]),
+            2.0 * (z * v[0] - x * v[2]),
+            2.0 * (x * v[1] - y * v[0]),
+        ];
+        [
+            v[0] + w * t[0] + (y * t[2] - z * t[1]) + self.translation[0],
+            v[1] + w * t[1] + (z * t[0] - x * t[2]) + self.translation[1],
+            v[2] + w * t[2] + (x * t[1] - y * t[0]) + self.translation[2],
+        ]
+    }
+
+    /// Transforms a Gaussian: its position is mapped, its scales are multiplied by `scale`, the
+    /// rotation is composed in front of its own orientation and its normal is rotated.
+    pub fn apply(&self, p: PlyGaussian) -> PlyGaussian {
+        self.apply_as(p, ActivationMode::Raw)
+    }
+
+    /// Transforms a Gaussian like `apply`, with scales stored as described by `activation`.
+    pub fn apply_as(&self, mut p: PlyGaussian, activation: ActivationMode) -> PlyGaussian {
+        if self.is_identity() {
+            return p;
+        }
+
+        [p.x, p.y, p.z] = self.apply_to_point([p.x, p.y, p.z]);
+
+        match activation {
+            // Raw scales are stored in log space
+            ActivationMode::Raw => {
+                let log_scale = self.scale.ln();
+                p.scale_0 += log_scale;
+                p.scale_1 += log_scale;
+                p.scale_2 += log_scale;
+            }
+            ActivationMode::PreActivated => {
+                p.scale_0 *= self.scale;
+                p.scale_1 *= self.scale;
+                p.scale_2 *= self.scale;
+            }
+        }
+
+        let [aw, ax, ay, az] = self.unit_rotation();
+        let [bw, bx, by, bz] = [p.rot_0, p.rot_1, p.rot_2, p.rot_3];
+        p.rot_0 = aw * bw - ax * bx - ay * by - az * bz;
+        p.rot_1 = aw * bx + ax * bw + ay * bz - az * by;
+        p.rot_2 = aw * by - ax * bz + ay * bw + az * bx;
+        p.rot_3 = aw * bz + ax * by - ay * bx + az * bw;
+
+        // Normals only rotate
+        let rotation = Self {
+            translation: [0.0; 3],
+            scale: 1.0,
+            ..*self
+        };
+        [p.nx, p.ny, p.nz] = rotation.apply_to_point(p.normal());
+        p
+    }
+}
+
+/// Converts Gaussians into splats after applying `transform` to each of them.
+///
+/// # Arguments
+/// * `ply_points` - A vector of raw `PlyGaussian` data.
+/// * `sort` - If true, sorts the splats by importance (volume * opacity).
+/// * `transform` - Transform applied before quantization.
+pub fn ply_to_splat_transformed(
+    ply_points: Vec<PlyGaussian>,
+    sort: bool,
+    transform: &Transform,
+) -> Vec<SplatPoint> {
+    ply_to_splat_with_options(
+        ply_points,
+        &ConvertOptions {
+            sort,
+            transform: *transform,
+            ..Default::default()
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ply_to_splat, splats_to_bytes};
+
+    fn scene() -> Vec<PlyGaussian> {
+        (0..6)
+            .map(|i| PlyGaussian {
+                x: i as f32 * 0.3 - 1.0,
+                y: -(i as f32) * 0.7,
+                z: if i % 2 == 0 { -0.0 } else { 2.5 },
+                opacity: i as f32 - 3.0,
+                scale_0: -1.0,
+                scale_1: i as f32 * 0.1,
+                rot_0: 0.9,
+                rot_1: 0.1 * i as f32,
+                rot_3: -0.2,
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_identity_is_unchanged() {
+        for sort in [false, true] {
+            assert_eq!(
+                splats_to_bytes(&ply_to_splat_transformed(
+                    scene(),
+                    sort,
+                    &Transform::IDENTITY
+                )),
+                splats_to_bytes(&ply_to_splat(scene(), sort))
+            );
+        }
+    }
+
+    #[test]
+    fn test_translation() {
+        let transform = Transform {
+            translation: [1.5, -2.0, 0.25],
+            ..Default::default()
+        };
+        let original = ply_to_splat(scene(), false);
+        let moved = ply_to_splat_transformed(scene(), false, &transform);
+        for (a, b) in original.iter().zip(&moved) {
+            assert_eq!(b.pos, [a.pos[0] + 1.5, a.pos[1] - 2.0, a.pos[2] + 0.25]);
+            assert_eq!((a.scale, a.color, a.rot), (b.scale, b.color, b.rot));
+        }
+    }
+
+    #[test]
+    fn test_rotation_and_scale() {
+        // 90° about X maps +Y to +Z
+        let half = std::f32::consts::FRAC_1_SQRT_2;
+        let transform = Transform {
+            rotation: [half, half, 0.0, 0.0],
+            scale: 2.0,
+            ..Default::default()
+        };
+        let p = transform.apply(PlyGaussian {
+            y: 1.0,
+            rot_0: 1.0,
+            ..Default::default()
+        });
+        assert!(p.x.abs() < 1e-6 && p.y.abs() < 1e-6 && (p.z - 2.0).abs() < 1e-6);
+        assert!((p.scale_0 - 2f32.ln()).abs() < 1e-6);
+        for (a, b) in [p.rot_0, p.rot_1, p.rot_2, p.rot_3]
+            .iter()
+            .zip([half, half, 0.0, 0.0])
+        {
+            assert!((a - b).abs() < 1e-6);
+        }
+    }
+}
@@ -0,0 +1,141 @@
+//! World unit conversion between viewers that assume meters, centimeters or millimeters.
+//!
+//! Converting units multiplies both positions and Gaussian scales by the same factor, so the
+//! scene stays geometrically consistent: splats keep their size relative to the distances
+//! between them.
+
+use crate::Transform;
+use anyhow::{Context, Result, bail};
+use std::path::Path;
+use std::str::FromStr;
+
+/// A unit of length for scene coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LengthUnit {
+    /// Meters (`m`), the usual unit of captured scenes.
+    #[default]
+    Meters,
+    /// Centimeters (`cm`).
+    Centimeters,
+    /// Millimeters (`mm`).
+    Millimeters,
+}
+
+impl LengthUnit {
+    /// Short symbol of the unit (`m`, `cm` or `mm`).
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Self::Meters => "m",
+            Self::Centimeters => "cm",
+            Self::Millimeters => "mm",
+        }
+    }
+
+    fn millimeters(self) -> f32 {
+        match self {
+            Self::Meters => 1000.0,
+            Self::Centimeters => 10.0,
+            Self::Millimeters => 1.0,
+        }
+    }
+
+    /// Factor converting lengths in `self` into lengths in `target`, e.g. 100 from m to cm.
+    pub fn factor_to(self, target: LengthUnit) -> f32 {
+        self.millimeters() / target.millimeters()
+    }
+}
+
+impl FromStr for LengthUnit {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "m" => Ok(Self::Meters),
+            "cm" => Ok(Self::Centimeters),
+            "mm" => Ok(Self::Millimeters),
+            _ => bail!("Unknown unit '{s}', expected m, cm or mm"),
+        }
+    }
+}
+
+impl Transform {
+    /// The uniform scaling converting a scene from `source` into `target` units.
+    ///
+    /// Identical units give `Transform::IDENTITY`.
+    pub fn from_units(source: LengthUnit, target: LengthUnit) -> Self {
+        Self {
+            scale: source.factor_to(target),
+            ..Self::IDENTITY
+        }
+    }
+}
+
+/// Builds the metadata sidecar recording the unit of a converted scene.
+pub fn units_sidecar_json(unit: LengthUnit) -> String {
+    format!("{{\n  \"units\": \"{}\"\n}}\n", unit.symbol())
+}
+
+/// Writes the units sidecar to `path`.
+pub fn save_units_sidecar<P: AsRef<Path>>(path: P, unit: LengthUnit) -> Result<()> {
+    std::fs::write(path, units_sidecar_json(unit)).context("Failed to write units sidecar")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{PlyGaussian, ply_to_splat, ply_to_splat_transformed};
+
+    fn scene() -> Vec<PlyGaussian> {
+        vec![
+            PlyGaussian {
+                x: 1.5,
+                y: -0.25,
+                z: 3.0,
+                scale_0: -2.0,
+                scale_1: -1.0,
+                scale_2: 0.5,
+                rot_0: 1.0,
+                ..Default::default()
+            },
+            PlyGaussian {
+                x: -4.0,
+                y: 0.125,
+                z: 0.75,
+                scale_0: -3.0,
+                rot_0: 0.5,
+                rot_2: 0.5,
+                ..Default::default()
+            },
+        ]
+    }
+
+    #[test]
+    fn test_meters_to_centimeters() {
+        let transform = Transform::from_units(LengthUnit::Meters, LengthUnit::Centimeters);
+        assert_eq!(transform.scale, 100.0);
+
+        let original = ply_to_splat(scene(), false);
+        let converted = ply_to_splat_transformed(scene(), false, &transform);
+        for (a, b) in original.iter().zip(&converted) {
+            assert_eq!(b.pos, a.pos.map(|c| c * 100.0));
+            for (sa, sb) in a.scale.iter().zip(b.scale) {
+                assert!((sb / (sa * 100.0) - 1.0).abs() < 1e-5);
+            }
+            assert_eq!(a.rot, b.rot);
+        }
+    }
+
+    #[test]
+    fn test_same_units_is_identity() {
+        for unit in [
+            LengthUnit::Meters,
+            LengthUnit::Centimeters,
+            LengthUnit::Millimeters,
+        ] {
+            assert!(Transform::from_units(unit, unit).is_identity());
+            assert_eq!(unit.symbol().parse::<LengthUnit>().unwrap(), unit);
+        }
+        assert!("km".parse::<LengthUnit>().is_err());
+        assert_eq!(LengthUnit::Millimeters.factor_to(LengthUnit::Meters), 0.001);
+    }
+}
@@ -0,0 +1,179 @@
+//! Header validation, so files with other naming schemes fail loudly instead of silently
+//! producing default (zero) fields.
+
+use crate::{GAUSSIAN_PROPERTIES, LoadWarning, Ply2SplatError, WarningHandler};
+use ply_rs::ply::Header;
+
+/// Checks that the header's `vertex` element declares all 14 properties read into
+/// `PlyGaussian`.
+///
+/// A header without a `vertex` element reports all of them as missing. Property types are
+/// not checked here; unsupported types only produce a warning when loading.
+pub fn validate_gaussian_header(header: &Header) -> Result<(), Ply2SplatError> {
+    validate_gaussian_element(header, "vertex")
+}
+
+/// Checks the Gaussian properties like `validate_gaussian_header`, on the element `name`.
+pub(crate) fn validate_gaussian_element(header: &Header, name: &str) -> Result<(), Ply2SplatError> {
+    let vertex = header.elements.get(name);
+    let missing: Vec<String> = GAUSSIAN_PROPERTIES
+        .into_iter()
+        .filter(|name| !vertex.is_some_and(|v| v.properties.contains_key(*name)))
+        .map(String::from)
+        .collect();
+    if missing.is_empty() {
+        Ok(())
+    } else {
+        Err(Ply2SplatError::MissingProperties(missing))
+    }
+}
+
+/// Compares the Gaussian count `declared` in the header with the `actual` number read.
+///
+/// A mismatch means the file was truncated. It is an error when `strict`, and otherwise
+/// only reported to `on_warning`, keeping the Gaussians that were read.
+pub(crate) fn check_vertex_count(
+    declared: usize,
+    actual: usize,
+    strict: bool,
+    on_warning: &WarningHandler,
+) -> Result<(), Ply2SplatError> {
+    if actual == declared {
+        Ok(())
+    } else if strict {
+        Err(Ply2SplatError::CountMismatch { declared, actual })
+    } else {
+        on_warning.warn(LoadWarning::CountMismatch { declared, actual });
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{LoadOptions, load_ply, load_ply_from_bytes, load_ply_from_bytes_with_options};
+
+    const NO_OPACITY: &[u8] = b"ply
+format ascii 1.0
+element vertex 1
+property float x
+property float y
+property float z
+property float f_dc_0
+property float f_dc_1
+property float f_dc_2
+property float scale_0
+property float scale_1
+property float scale_2
+property float rot_0
+property float rot_1
+property float rot_2
+property float rot_3
+end_header
+1 2 3 0 0 0 0 0 0 1 0 0 0
+";
+
+    #[test]
+    fn test_missing_opacity() {
+        let err = load_ply_from_bytes(NO_OPACITY).unwrap_err();
+        assert!(matches!(&err, Ply2SplatError::MissingProperties(m) if m == &["opacity"]));
+        assert_eq!(
+            err.to_string(),
+            "PLY vertex element is missing 1 of 14 expected properties: opacity"
+        );
+    }
+
+    #[test]
+    fn test_allow_missing() {
+        let options = LoadOptions {
+            allow_missing: true,
+            ..Default::default()
+        };
+        let points = load_ply_from_bytes_with_options(NO_OPACITY, &options).unwrap();
+        assert_eq!(points.len(), 1);
+        assert_eq!((points[0].x, points[0].opacity), (1.0, 0.0));
+    }
+
+    #[test]
+    fn test_missing_vertex_element() {
+        let ply = b"ply\nformat ascii 1.0\nelement face 0\nproperty list uchar int vertex_indices\nend_header\n";
+        let err = load_ply_from_bytes(ply).unwrap_err();
+        assert!(matches!(&err, Ply2SplatError::MissingElement { name, .. } if name == "vertex"));
+        assert_eq!(
+            err.to_string(),
+            "PLY has no 'vertex' element (available: face)"
+        );
+    }
+
+    #[test]
+    fn test_named_element() {
+        let mut ply = "ply\nformat ascii 1.0\nelement point 1\n".to_string();
+        for name in GAUSSIAN_PROPERTIES {
+            ply += &format!("property float {name}\n");
+        }
+        ply += "end_header\n1 2 3 0 0 0 0 0 0 0 1 0 0 0\n";
+        let options = LoadOptions {
+            element: Some("point".to_string()),
+            ..Default::default()
+        };
+        let points = load_ply_from_bytes_with_options(ply.as_bytes(), &options).unwrap();
+        assert_eq!(points[0].x, 1.0);
+
+        let err = load_ply_from_bytes(ply.as_bytes()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "PLY has no 'vertex' element (available: point)"
+        );
+    }
+
+    #[test]
+    fn test_count_mismatch() {
+        // The header announces 3 vertices, but the payload stops after 2
+        let mut ply = "ply\nformat ascii 1.0\nelement vertex 3\n".to_string();
+        for name in GAUSSIAN_PROPERTIES {
+            ply += &format!("property float {name}\n");
+        }
+        ply += "end_header\n1 2 3 0 0 0 0 0 0 0 1 0 0 0\n4 5 6 0 0 0 0 0 0 0 1 0 0 0\n";
+
+        let warnings = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = warnings.clone();
+        let options = LoadOptions {
+            on_warning: WarningHandler::new(move |w| sink.lock().unwrap().push(w.clone())),
+            ..Default::default()
+        };
+        let points = load_ply_from_bytes_with_options(ply.as_bytes(), &options).unwrap();
+        assert_eq!(points.len(), 2);
+        assert_eq!(points[1].x, 4.0);
+        assert_eq!(
+            *warnings.lock().unwrap(),
+            [LoadWarning::CountMismatch {
+                declared: 3,
+                actual: 2
+            }]
+        );
+
+        let options = LoadOptions {
+            strict: true,
+            ..Default::default()
+        };
+        let err = load_ply_from_bytes_with_options(ply.as_bytes(), &options).unwrap_err();
+        assert!(matches!(
+            err,
+            Ply2SplatError::CountMismatch {
+                declared: 3,
+                actual: 2
+            }
+        ));
+        assert_eq!(
+            err.to_string(),
+            "PLY header declares 3 vertices, but the payload holds only 2"
+        );
+    }
+
+    #[test]
+    fn test_nonexistent_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let err = load_ply(dir.path().join("missing.ply")).unwrap_err();
+        assert!(matches!(&err, Ply2SplatError::Io(e) if e.kind() == std::io::ErrorKind::NotFound));
+    }
+}
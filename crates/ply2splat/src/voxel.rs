@@ -0,0 +1,79 @@
+//! Voxel downsampling for lighter scenes on constrained devices.
+
+use crate::PlyGaussian;
+use std::collections::HashMap;
+
+/// Collapses all Gaussians whose centers fall into the same voxel to a single one.
+///
+/// Voxel `(i, j, k)` covers `[i * voxel_size, (i + 1) * voxel_size)` on the x axis and likewise
+/// on y and z. Each voxel keeps its most opaque Gaussian unchanged (the earliest one on ties)
+/// rather than an average, so colors, rotations and spherical harmonics stay consistent.
+/// Survivors keep their original relative order.
+///
+/// # Panics
+/// Panics if `voxel_size` is not positive.
+pub fn downsample_voxel(points: Vec<PlyGaussian>, voxel_size: f32) -> Vec<PlyGaussian> {
+    assert!(voxel_size > 0.0, "voxel_size must be positive");
+
+    let mut best: HashMap<(i64, i64, i64), usize> = HashMap::new();
+    for (i, p) in points.iter().enumerate() {
+        let voxel = (
+            (p.x / voxel_size).floor() as i64,
+            (p.y / voxel_size).floor() as i64,
+            (p.z / voxel_size).floor() as i64,
+        );
+        best.entry(voxel)
+            .and_modify(|kept| {
+                if p.opacity > points[*kept].opacity {
+                    *kept = i;
+                }
+            })
+            .or_insert(i);
+    }
+
+    // Decide by original index so the result never depends on hash map order
+    let mut keep = vec![false; points.len()];
+    for i in best.into_values() {
+        keep[i] = true;
+    }
+    points
+        .into_iter()
+        .zip(keep)
+        .filter_map(|(p, keep)| keep.then_some(p))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(x: f32, y: f32, z: f32, opacity: f32) -> PlyGaussian {
+        PlyGaussian {
+            x,
+            y,
+            z,
+            opacity,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_same_voxel_collapses() {
+        let points = vec![
+            point(0.1, 0.1, 0.1, -1.0),
+            point(2.5, 0.0, 0.0, 0.0),
+            point(0.9, 0.2, 0.5, 3.0),
+            point(-0.5, 0.0, 0.0, 1.0),
+        ];
+        let kept = downsample_voxel(points, 1.0);
+        let xs: Vec<f32> = kept.iter().map(|p| p.x).collect();
+        // The first and third share voxel (0, 0, 0); the more opaque third one survives
+        assert_eq!(xs, [2.5, 0.9, -0.5]);
+    }
+
+    #[test]
+    fn test_distinct_voxels_preserved() {
+        let points: Vec<PlyGaussian> = (0..10).map(|i| point(i as f32, 0.0, 0.0, 0.0)).collect();
+        assert_eq!(downsample_voxel(points, 0.5).len(), 10);
+    }
+}
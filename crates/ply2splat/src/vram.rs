@@ -0,0 +1,85 @@
+//! Rough GPU memory estimates for converted scenes.
+
+/// Describes what a renderer allocates on the GPU per splat.
+///
+/// The numbers are per-splat byte counts; `estimate_vram` multiplies them by the splat count
+/// and adds `fixed_bytes`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RendererProfile {
+    /// Human-readable name of the profile.
+    pub name: &'static str,
+    /// Bytes of the splat data buffer/texture per splat (32 for the raw SPLAT layout).
+    pub splat_bytes: usize,
+    /// Bytes of sort state per splat (sort indices, depth keys and any ping-pong copies).
+    pub sort_bytes: usize,
+    /// Bytes of per-frame intermediate data per splat (e.g. projected 2D splats).
+    pub intermediate_bytes: usize,
+    /// Allocation independent of the splat count (uniforms, tile lists, histograms, ...).
+    pub fixed_bytes: usize,
+}
+
+impl RendererProfile {
+    /// WebGL viewers in the style of antimatter15/splat: the 32-byte records live in a data
+    /// texture, the CPU sorts and uploads a `u32` index per splat.
+    pub const WEBGL_TEXTURE: RendererProfile = RendererProfile {
+        name: "WebGL texture (CPU sort)",
+        splat_bytes: 32,
+        sort_bytes: 4,
+        intermediate_bytes: 0,
+        fixed_bytes: 0,
+    };
+
+    /// Compute-based renderers with a GPU radix sort: the 32-byte storage buffer, `u32` depth
+    /// keys and `u32` indices, each double-buffered for the sort passes, plus a projected
+    /// 2D splat (position, conic, color) of 32 bytes.
+    pub const GPU_RADIX_SORT: RendererProfile = RendererProfile {
+        name: "Compute (GPU radix sort)",
+        splat_bytes: 32,
+        sort_bytes: 16,
+        intermediate_bytes: 32,
+        fixed_bytes: 1024 * 1024,
+    };
+
+    /// Built-in profiles, in the order the CLI reports them.
+    pub const ALL: [RendererProfile; 2] = [Self::WEBGL_TEXTURE, Self::GPU_RADIX_SORT];
+
+    /// Total bytes allocated per splat.
+    pub fn bytes_per_splat(&self) -> usize {
+        self.splat_bytes + self.sort_bytes + self.intermediate_bytes
+    }
+}
+
+/// Estimates the GPU memory in bytes needed to render `splat_count` splats with `profile`.
+///
+/// This is simple arithmetic over the profile and ignores driver overhead and alignment,
+/// so treat the result as a lower bound.
+pub fn estimate_vram(splat_count: usize, profile: &RendererProfile) -> usize {
+    profile.fixed_bytes + splat_count * profile.bytes_per_splat()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimate_vram() {
+        assert_eq!(
+            estimate_vram(1_000_000, &RendererProfile::WEBGL_TEXTURE),
+            36_000_000
+        );
+        assert_eq!(
+            estimate_vram(1_000, &RendererProfile::GPU_RADIX_SORT),
+            1024 * 1024 + 80_000
+        );
+
+        let custom = RendererProfile {
+            name: "custom",
+            splat_bytes: 20,
+            sort_bytes: 8,
+            intermediate_bytes: 4,
+            fixed_bytes: 100,
+        };
+        assert_eq!(estimate_vram(10, &custom), 420);
+        assert_eq!(estimate_vram(0, &custom), 100);
+    }
+}
@@ -1,5 +1,6 @@
 use assert_cmd::Command;
 use ply2splat::SplatPoint;
+use predicates::prelude::*;
 use sha2::{Digest, Sha256};
 use std::fs;
 use std::io::Write;
@@ -86,7 +87,13 @@ fn run_dataset_test(
     expected_output_hash: &str,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let cache_dir = get_cache_dir();
-    let file_name = url.split('/').last().unwrap().split('?').next().unwrap();
+    let file_name = url
+        .split('/')
+        .next_back()
+        .unwrap()
+        .split('?')
+        .next()
+        .unwrap();
     let cached_file_path = cache_dir.join(file_name);
 
     // 1. Check Cache
@@ -165,3 +172,220 @@ fn test_dataset_drjohnson() -> Result<(), Box<dyn std::error::Error>> {
         "1fa57e61226e54c0461de1535b77cd0c5264ec8c586e9ca2ff1ff6a5ab8fd2c2",
     )
 }
+
+#[test]
+#[allow(deprecated)]
+fn test_cli_batch_conversion() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let ply = "ply\nformat ascii 1.0\nelement vertex 2\nproperty float x\nproperty float opacity\nend_header\n0.0 1.0\n1.0 0.5\n";
+    fs::write(dir.path().join("a.ply"), ply)?;
+    fs::write(dir.path().join("b.ply"), ply)?;
+    fs::write(dir.path().join("c.ply"), "not a ply file")?;
+    let out_dir = dir.path().join("out");
+
+    // The bad file is reported but does not stop the others
+    Command::new(assert_cmd::cargo::cargo_bin("ply2splat"))
+        .arg("--input")
+        .arg(dir.path().join("*.ply"))
+        .arg("--output-dir")
+        .arg(&out_dir)
+        .arg("--parallel-files")
+        .arg("--lenient")
+        .assert()
+        .failure()
+        .stdout(predicates::str::contains("a.ply: 2 vertices -> 2 splats"))
+        .stdout(predicates::str::contains("Converted 2 of 3 files"))
+        .stdout(predicates::str::contains("c.ply: failed").not())
+        .stderr(predicates::str::contains("c.ply: failed"));
+    assert_eq!(fs::read(out_dir.join("a.splat"))?.len(), 64);
+    assert_eq!(fs::read(out_dir.join("b.splat"))?.len(), 64);
+
+    // With --fail-fast nothing after the bad file is converted
+    let fail_fast_dir = dir.path().join("fail_fast");
+    Command::new(assert_cmd::cargo::cargo_bin("ply2splat"))
+        .arg("--input")
+        .arg(dir.path().join("c.ply"))
+        .arg(dir.path().join("a.ply"))
+        .arg("--output-dir")
+        .arg(&fail_fast_dir)
+        .arg("--fail-fast")
+        .arg("--lenient")
+        .assert()
+        .failure();
+    assert!(!fail_fast_dir.join("a.splat").exists());
+
+    // Inputs sharing a file stem would overwrite each other and are rejected up front
+    let nested = dir.path().join("nested");
+    fs::create_dir_all(&nested)?;
+    fs::write(nested.join("a.ply"), ply)?;
+    let collide_dir = dir.path().join("collide");
+    Command::new(assert_cmd::cargo::cargo_bin("ply2splat"))
+        .arg("--input")
+        .arg(dir.path().join("a.ply"))
+        .arg(nested.join("a.ply"))
+        .arg("--output-dir")
+        .arg(&collide_dir)
+        .arg("--lenient")
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("same output file"));
+    assert!(!collide_dir.join("a.splat").exists());
+
+    // Several inputs with --output are merged into one file
+    let merged = dir.path().join("merged.splat");
+    Command::new(assert_cmd::cargo::cargo_bin("ply2splat"))
+        .arg("--input")
+        .arg(dir.path().join("a.ply"))
+        .arg(dir.path().join("b.ply"))
+        .arg("--output")
+        .arg(&merged)
+        .arg("--lenient")
+        .assert()
+        .success();
+    assert_eq!(fs::read(&merged)?.len(), 4 * 32);
+
+    // Without --lenient the incomplete header is rejected
+    Command::new(assert_cmd::cargo::cargo_bin("ply2splat"))
+        .arg("--input")
+        .arg(dir.path().join("a.ply"))
+        .arg("--output")
+        .arg(dir.path().join("strict.splat"))
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "missing 12 of 14 expected properties: y, z",
+        ));
+
+    Ok(())
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_cli_stdin_stdout() -> Result<(), Box<dyn std::error::Error>> {
+    let ply = "ply\nformat ascii 1.0\nelement vertex 3\nproperty float x\nproperty float opacity\nend_header\n0.0 1.0\n1.0 0.5\n2.0 0.0\n";
+
+    // Only the splat bytes go to stdout; status lines go to stderr
+    let output = Command::new(assert_cmd::cargo::cargo_bin("ply2splat"))
+        .args(["--input", "-", "--output", "-", "--lenient"])
+        .write_stdin(ply)
+        .assert()
+        .success()
+        .stderr(predicates::str::contains("Total time"))
+        .get_output()
+        .stdout
+        .clone();
+    assert_eq!(output.len(), 3 * 32);
+
+    Ok(())
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_cli_element() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let input = dir.path().join("point.ply");
+    let output = dir.path().join("point.splat");
+    fs::write(
+        &input,
+        "ply\nformat ascii 1.0\nelement point 2\nproperty float x\nproperty float opacity\nend_header\n0.0 1.0\n1.0 0.5\n",
+    )?;
+
+    let convert = |element: &[&str]| {
+        let mut cmd = Command::new(assert_cmd::cargo::cargo_bin("ply2splat"));
+        cmd.arg("--input")
+            .arg(&input)
+            .arg("--output")
+            .arg(&output)
+            .arg("--lenient")
+            .args(element);
+        cmd.assert()
+    };
+
+    convert(&[]).failure().stderr(predicates::str::contains(
+        "PLY has no 'vertex' element (available: point)",
+    ));
+    convert(&["--element", "point"]).success();
+    assert_eq!(fs::read(&output)?.len(), 64);
+
+    // The schema only describes `vertex`, so it is rejected before converting
+    fs::remove_file(&output)?;
+    convert(&["--element", "point", "--keep-schema"])
+        .failure()
+        .stderr(predicates::str::contains("cannot be used with"));
+    assert!(!output.exists());
+
+    Ok(())
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_cli_compare_needs_offset() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let input = dir.path().join("a.ply");
+    let output = dir.path().join("ab.splat");
+    fs::write(
+        &input,
+        "ply\nformat ascii 1.0\nelement vertex 1\nproperty float x\nproperty float opacity\nend_header\n0.0 1.0\n",
+    )?;
+
+    let convert = |offset: &[&str]| {
+        let mut cmd = Command::new(assert_cmd::cargo::cargo_bin("ply2splat"));
+        cmd.arg("--input")
+            .arg(&input)
+            .arg("--compare")
+            .arg(&input)
+            .arg("--output")
+            .arg(&output)
+            .arg("--lenient")
+            .args(offset);
+        cmd.assert()
+    };
+
+    // Without an offset scene B would sit exactly on top of scene A
+    convert(&[])
+        .failure()
+        .stderr(predicates::str::contains("--compare-offset"));
+    convert(&["--compare-offset", "-5,0,0"]).success();
+    let splats = fs::read(&output)?;
+    assert_eq!(splats.len(), 64);
+    let xs: Vec<f32> = splats
+        .chunks(32)
+        .map(|s| f32::from_le_bytes(s[..4].try_into().unwrap()))
+        .collect();
+    assert!(xs.contains(&0.0) && xs.contains(&-5.0));
+
+    Ok(())
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_cli_drop_invalid() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    // ASCII PLY cannot spell NaN, so this one is binary
+    let mut ply = b"ply\nformat binary_little_endian 1.0\nelement vertex 2\nproperty float x\nproperty float opacity\nend_header\n".to_vec();
+    for v in [f32::NAN, 1.0, 2.0, 0.5] {
+        ply.extend_from_slice(&v.to_le_bytes());
+    }
+    let input = dir.path().join("nan.ply");
+    fs::write(&input, ply)?;
+
+    let convert = |flag: Option<&str>| -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        let output = dir.path().join("nan.splat");
+        let mut cmd = Command::new(assert_cmd::cargo::cargo_bin("ply2splat"));
+        cmd.arg("--input")
+            .arg(&input)
+            .arg("--output")
+            .arg(&output)
+            .arg("--lenient");
+        cmd.args(flag);
+        cmd.assert().success();
+        Ok(fs::read(&output)?)
+    };
+
+    assert_eq!(convert(None)?.len(), 64);
+    let dropped = convert(Some("--drop-invalid"))?;
+    assert_eq!(dropped.len(), 32);
+    assert_eq!(dropped[..4], 2.0f32.to_le_bytes());
+
+    Ok(())
+}
@@ -1,7 +1,10 @@
 #![no_main]
 use arbitrary::Arbitrary;
 use libfuzzer_sys::fuzz_target;
-use ply2splat::{PlyGaussian, ply_to_splat};
+use ply2splat::{
+    ConvertOptions, PlyGaussian, SanitizeMode, is_finite_gaussian, ply_to_splat,
+    ply_to_splat_with_options,
+};
 
 #[derive(Arbitrary, Debug)]
 struct Input {
@@ -38,9 +41,21 @@ fuzz_target!(|data: Input| {
         rot_1: data.rot_1,
         rot_2: data.rot_2,
         rot_3: data.rot_3,
+        ..Default::default()
     };
 
     // Ensure this doesn't panic even with extreme floats (NaN, Inf, etc.)
     // Testing both sorting enabled and disabled based on input
-    let _ = ply_to_splat(vec![p], data.sort);
+    let _ = ply_to_splat(vec![p.clone()], data.sort);
+
+    // Dropping invalid Gaussians never lets a non-finite position through
+    let options = ConvertOptions {
+        sort: data.sort,
+        sanitize: SanitizeMode::Drop,
+        ..Default::default()
+    };
+    let finite = is_finite_gaussian(&p);
+    let splats = ply_to_splat_with_options(vec![p], &options);
+    assert_eq!(splats.len(), usize::from(finite));
+    assert!(splats.iter().all(|s| s.pos.iter().all(|c| c.is_finite())));
 });
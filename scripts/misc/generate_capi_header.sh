@@ -0,0 +1,9 @@
+#!/usr/bin/env sh
+# Regenerates crates/ply2splat-capi/include/ply2splat.h from the crate's `extern "C"` items.
+# Needs the cbindgen CLI: cargo install cbindgen --version 0.29.4 --locked
+set -eu
+cd "$(dirname "$0")/../.."
+cbindgen \
+    --config crates/ply2splat-capi/cbindgen.toml \
+    --output crates/ply2splat-capi/include/ply2splat.h \
+    crates/ply2splat-capi